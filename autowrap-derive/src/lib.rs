@@ -0,0 +1,210 @@
+//! Derive macros for [`autowrap`](https://docs.rs/autowrap).
+//!
+//! Use them through `autowrap` with the `derive` feature enabled rather than
+//! depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Field, Fields, Index, Result};
+
+/// Generates a `<Name>Shared` struct whose fields are wrapped for sharing,
+/// plus `fn into_shared(self) -> <Name>Shared`.
+///
+/// Every field is wrapped in `Arc<Mutex<_>>` unless a `#[wrap(...)]`
+/// attribute picks another wrapper:
+///
+/// | Attribute | Field type |
+/// |-----------|------------|
+/// | `#[wrap(arc_mutex)]` (default) | `Arc<Mutex<T>>` |
+/// | `#[wrap(arc_rwlock)]` | `Arc<RwLock<T>>` |
+/// | `#[wrap(arc)]` | `Arc<T>` |
+/// | `#[wrap(rc_refcell)]` | `Rc<RefCell<T>>` |
+/// | `#[wrap(rc)]` | `Rc<T>` |
+///
+/// The generated struct keeps the original's visibility, generics and field
+/// visibilities, and implements `Clone` by cloning each pointer, so clones
+/// share every field with the original. `into_shared` is an inherent method
+/// and takes precedence over `autowrap::IntoShared::into_shared`.
+///
+/// Named and tuple structs are supported; enums and unions are rejected.
+///
+/// # Example
+///
+/// ```ignore
+/// use autowrap::WrapFields;
+///
+/// #[derive(WrapFields)]
+/// struct Config {
+///     name: String,
+///     #[wrap(arc_rwlock)]
+///     routes: Vec<String>,
+/// }
+///
+/// let shared = Config { name: "api".into(), routes: vec![] }.into_shared();
+/// let handle = shared.clone();
+/// handle.routes.write().unwrap().push("/health".into());
+/// assert_eq!(shared.routes.read().unwrap().len(), 1);
+/// ```
+#[proc_macro_derive(WrapFields, attributes(wrap))]
+pub fn derive_wrap_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+#[derive(Clone, Copy)]
+enum Wrapper {
+    ArcMutex,
+    ArcRwLock,
+    Arc,
+    RcRefCell,
+    Rc,
+}
+
+impl Wrapper {
+    const NAMES: &'static str = "`arc_mutex`, `arc_rwlock`, `arc`, `rc_refcell` or `rc`";
+
+    fn from_field(field: &Field) -> Result<Self> {
+        let mut wrapper = None;
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("wrap")) {
+            if wrapper.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate `#[wrap(...)]` attribute"));
+            }
+            attr.parse_nested_meta(|meta| {
+                if wrapper.is_some() {
+                    return Err(meta.error("expected a single wrapper"));
+                }
+                let path = &meta.path;
+                wrapper = Some(if path.is_ident("arc_mutex") {
+                    Wrapper::ArcMutex
+                } else if path.is_ident("arc_rwlock") {
+                    Wrapper::ArcRwLock
+                } else if path.is_ident("arc") {
+                    Wrapper::Arc
+                } else if path.is_ident("rc_refcell") {
+                    Wrapper::RcRefCell
+                } else if path.is_ident("rc") {
+                    Wrapper::Rc
+                } else {
+                    return Err(meta.error(format!(
+                        "unknown wrapper, expected one of {}",
+                        Wrapper::NAMES
+                    )));
+                });
+                Ok(())
+            })?;
+            if wrapper.is_none() {
+                return Err(Error::new_spanned(
+                    attr,
+                    format!("expected a wrapper: one of {}", Wrapper::NAMES),
+                ));
+            }
+        }
+        Ok(wrapper.unwrap_or(Wrapper::ArcMutex))
+    }
+
+    fn wrap_type(self, ty: &syn::Type) -> TokenStream2 {
+        match self {
+            Wrapper::ArcMutex => quote!(::std::sync::Arc<::std::sync::Mutex<#ty>>),
+            Wrapper::ArcRwLock => quote!(::std::sync::Arc<::std::sync::RwLock<#ty>>),
+            Wrapper::Arc => quote!(::std::sync::Arc<#ty>),
+            Wrapper::RcRefCell => quote!(::std::rc::Rc<::std::cell::RefCell<#ty>>),
+            Wrapper::Rc => quote!(::std::rc::Rc<#ty>),
+        }
+    }
+
+    fn wrap_value(self, value: TokenStream2) -> TokenStream2 {
+        match self {
+            Wrapper::ArcMutex => {
+                quote!(::std::sync::Arc::new(::std::sync::Mutex::new(#value)))
+            }
+            Wrapper::ArcRwLock => {
+                quote!(::std::sync::Arc::new(::std::sync::RwLock::new(#value)))
+            }
+            Wrapper::Arc => quote!(::std::sync::Arc::new(#value)),
+            Wrapper::RcRefCell => {
+                quote!(::std::rc::Rc::new(::std::cell::RefCell::new(#value)))
+            }
+            Wrapper::Rc => quote!(::std::rc::Rc::new(#value)),
+        }
+    }
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(data) => {
+            return Err(Error::new(
+                data.enum_token.span,
+                "`WrapFields` can only be derived for structs",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "`WrapFields` can only be derived for structs",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let shared = format_ident!("{}Shared", name);
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut decls = Vec::new();
+    let mut inits = Vec::new();
+    let mut clones = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let wrapper = Wrapper::from_field(field)?;
+        let field_vis = &field.vis;
+        let ty = wrapper.wrap_type(&field.ty);
+        let member = match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = Index {
+                    index: i as u32,
+                    span: Span::call_site(),
+                };
+                quote!(#index)
+            }
+        };
+        let value = wrapper.wrap_value(quote!(self.#member));
+        match &field.ident {
+            Some(ident) => decls.push(quote!(#field_vis #ident: #ty)),
+            None => decls.push(quote!(#field_vis #ty)),
+        }
+        inits.push(quote!(#member: #value));
+        clones.push(quote!(#member: ::std::clone::Clone::clone(&self.#member)));
+    }
+
+    let doc = format!("Shared form of [`{}`], generated by `#[derive(WrapFields)]`.", name);
+    let body = match fields {
+        Fields::Named(_) => quote!({ #(#decls,)* }),
+        Fields::Unnamed(_) => quote!(( #(#decls,)* ) #where_clause;),
+        Fields::Unit => quote!(#where_clause;),
+    };
+    let where_before_body = match fields {
+        Fields::Named(_) => quote!(#where_clause),
+        _ => quote!(),
+    };
+    let generics = &input.generics;
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #shared #generics #where_before_body #body
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Wraps every field for sharing; see `#[derive(WrapFields)]`.
+            #vis fn into_shared(self) -> #shared #ty_generics {
+                #shared { #(#inits,)* }
+            }
+        }
+
+        impl #impl_generics ::std::clone::Clone for #shared #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                #shared { #(#clones,)* }
+            }
+        }
+    })
+}
@@ -0,0 +1,143 @@
+//! A copy-on-write handle around `Arc<T>`.
+
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Shared, immutable-by-default data that clones itself on the first write
+/// while shared.
+///
+/// Cloning an `ArcCow` clones only the `Arc`. [`make_mut`](ArcCow::make_mut)
+/// is `Arc::make_mut`: if this handle is the only owner it returns the value
+/// in place, otherwise it first clones `T` into a fresh allocation that this
+/// handle then owns alone, leaving every other clone untouched.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::WrapExt;
+///
+///     let defaults = vec![1, 2].arc_cow();
+///     let mut custom = defaults.clone();
+///     custom.make_mut().push(3);
+///
+///     assert_eq!(*defaults.get(), [1, 2]);
+///     assert_eq!(*custom.get(), [1, 2, 3]);
+/// }
+/// ```
+pub struct ArcCow<T> {
+    inner: Arc<T>,
+}
+
+impl<T> ArcCow<T> {
+    /// Creates a handle owning `value`.
+    #[inline(always)]
+    pub fn new(value: T) -> Self {
+        ArcCow {
+            inner: Arc::new(value),
+        }
+    }
+
+    /// Returns a shared reference to the value.
+    #[inline(always)]
+    pub fn get(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns `true` if both handles refer to the same allocation.
+    #[inline(always)]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.inner, &other.inner)
+    }
+
+    /// Returns the underlying `Arc<T>`.
+    #[inline(always)]
+    pub fn into_arc(self) -> Arc<T> {
+        self.inner
+    }
+}
+
+impl<T: Clone> ArcCow<T> {
+    /// Returns a mutable reference to the value, cloning it first if any
+    /// other `Arc` or `Weak` refers to it.
+    #[inline(always)]
+    pub fn make_mut(&mut self) -> &mut T {
+        Arc::make_mut(&mut self.inner)
+    }
+
+    /// Returns the value, cloning it only if it is still shared.
+    #[inline]
+    pub fn into_owned(self) -> T {
+        Arc::try_unwrap(self.inner).unwrap_or_else(|shared| (*shared).clone())
+    }
+}
+
+impl<T> Deref for ArcCow<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> Clone for ArcCow<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        ArcCow {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: Default> Default for ArcCow<T> {
+    #[inline]
+    fn default() -> Self {
+        ArcCow::new(T::default())
+    }
+}
+
+impl<T> From<Arc<T>> for ArcCow<T> {
+    #[inline(always)]
+    fn from(inner: Arc<T>) -> Self {
+        ArcCow { inner }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArcCow<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArcCow").field(&*self.inner).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_make_mut_clones_only_when_shared() {
+        let mut a = ArcCow::new(vec![1u8]);
+        let b = a.clone();
+        assert!(ArcCow::ptr_eq(&a, &b));
+
+        a.make_mut().push(2);
+        assert!(!ArcCow::ptr_eq(&a, &b));
+        assert_eq!(*a.get(), [1, 2]);
+        assert_eq!(*b.get(), [1]);
+
+        let before = Arc::as_ptr(&a.inner);
+        a.make_mut().push(3);
+        assert_eq!(Arc::as_ptr(&a.inner), before);
+        assert_eq!(a.into_owned(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_into_owned_clones_shared_value() {
+        let a = ArcCow::new(String::from("shared"));
+        let b = a.clone();
+        assert_eq!(a.into_owned(), "shared");
+        assert_eq!(Arc::strong_count(&b.into_arc()), 1);
+    }
+}
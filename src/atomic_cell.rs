@@ -0,0 +1,315 @@
+//! Atomic storage for small `Copy` values, with a locked fallback.
+
+use std::fmt;
+use std::mem;
+use std::sync::atomic::Ordering;
+use std::sync::PoisonError;
+
+use crate::primitives::{AtomicU16, AtomicU32, AtomicU8, Mutex};
+#[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+use crate::primitives::AtomicU64;
+
+enum Repr<T> {
+    U8(AtomicU8),
+    U16(AtomicU16),
+    U32(AtomicU32),
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+    U64(AtomicU64),
+    Locked(Mutex<T>),
+}
+
+/// Reinterprets `value` as `U`; only called with types of identical size.
+///
+/// # Safety
+///
+/// Every byte of `T` must be initialized, which [`NoPadding`] guarantees.
+#[inline(always)]
+unsafe fn bits<T: Copy, U: Copy>(value: T) -> U {
+    debug_assert_eq!(mem::size_of::<T>(), mem::size_of::<U>());
+    // SAFETY: both types are `Copy` and the same size, the caller guarantees
+    // no byte is uninitialized, and `transmute_copy` reads unaligned, so
+    // `T`'s alignment doesn't matter.
+    unsafe { mem::transmute_copy(&value) }
+}
+
+/// Marker for `Copy` types whose every byte is initialized, so a value can be
+/// stored bit-for-bit in an integer atomic.
+///
+/// Implemented for the primitive integers, floats, `bool`, `char` and arrays
+/// of them. Tuples are not covered: the compiler may insert padding between
+/// their fields.
+///
+/// # Safety
+///
+/// The type must contain no padding bytes and no other uninitialized
+/// memory, in any value. A `#[repr(C)]` or `#[repr(transparent)]` struct
+/// whose fields are all `NoPadding` and laid out without gaps qualifies.
+pub unsafe trait NoPadding: Copy {}
+
+macro_rules! impl_no_padding {
+    ($($ty:ty),* $(,)?) => {
+        // SAFETY: primitive types have no padding bytes.
+        $(unsafe impl NoPadding for $ty {})*
+    };
+}
+
+impl_no_padding!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char);
+
+// SAFETY: array elements are laid out back to back without gaps.
+unsafe impl<T: NoPadding, const N: usize> NoPadding for [T; N] {}
+
+/// A thread-safe cell for `Copy` values that is lock-free whenever the value
+/// fits a native atomic.
+///
+/// For [`NoPadding`] types whose size is exactly 1, 2, 4 or 8 bytes,
+/// [`new`](AtomicCell::new) stores the value bit-for-bit in the matching
+/// `AtomicU8`/`AtomicU16`/`AtomicU32`/`AtomicU64`, so `load`, `store` and
+/// `swap` are single atomic instructions. Alignment doesn't matter since
+/// values are copied in and out rather than referenced in place. Every other
+/// size, including zero-sized types, falls back to a `Mutex<T>`, as do
+/// 8-byte types on targets without 64-bit atomics unless the
+/// `portable-atomic` feature is enabled;
+/// [`is_lock_free`](AtomicCell::is_lock_free) tells the two apart. Types
+/// that may contain padding, such as `(u8, u16)`, can't be reinterpreted as
+/// integers and always use the mutex through
+/// [`new_locked`](AtomicCell::new_locked).
+///
+/// # Example
+///
+/// ```
+/// #[cfg(all(feature = "sync", not(feature = "loom")))]
+/// {
+///     use autowrap::AtomicCell;
+///
+///     let range = AtomicCell::new([10u32, 20]);
+///     assert!(AtomicCell::<[u32; 2]>::is_lock_free());
+///
+///     range.store([5, 15]);
+///     assert_eq!(range.swap([0, 1]), [5, 15]);
+///     assert_eq!(range.load(), [0, 1]);
+///
+///     let padded = AtomicCell::new_locked((1u8, 2u16));
+///     assert_eq!(padded.load(), (1, 2));
+/// }
+/// ```
+pub struct AtomicCell<T: Copy> {
+    repr: Repr<T>,
+}
+
+impl<T: NoPadding> AtomicCell<T> {
+    /// Creates a new cell holding `value`, lock-free if `T` fits a native
+    /// atomic.
+    pub fn new(value: T) -> Self {
+        // SAFETY (all arms): `T: NoPadding`, so every byte is initialized.
+        let repr = match mem::size_of::<T>() {
+            1 => Repr::U8(AtomicU8::new(unsafe { bits(value) })),
+            2 => Repr::U16(AtomicU16::new(unsafe { bits(value) })),
+            4 => Repr::U32(AtomicU32::new(unsafe { bits(value) })),
+            #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+            8 => Repr::U64(AtomicU64::new(unsafe { bits(value) })),
+            _ => Repr::Locked(Mutex::new(value)),
+        };
+        AtomicCell { repr }
+    }
+
+    /// Returns `true` if [`AtomicCell::new`] backs `T` with a native atomic
+    /// rather than a mutex.
+    #[inline(always)]
+    pub const fn is_lock_free() -> bool {
+        let size = mem::size_of::<T>();
+        matches!(size, 1 | 2 | 4) || (size == 8 && cfg!(any(target_has_atomic = "64", feature = "portable-atomic")))
+    }
+}
+
+impl<T: Copy> AtomicCell<T> {
+    /// Creates a new cell holding `value` behind a mutex, for types that
+    /// aren't [`NoPadding`].
+    pub fn new_locked(value: T) -> Self {
+        AtomicCell {
+            repr: Repr::Locked(Mutex::new(value)),
+        }
+    }
+
+    /// Loads the current value (`Acquire` on the lock-free path).
+    #[inline]
+    pub fn load(&self) -> T {
+        // SAFETY (all `bits` calls below): the integer variants are only
+        // built by `new`, which requires `T: NoPadding`, and an atomic
+        // only ever holds bits that came from a `T`.
+        match &self.repr {
+            Repr::U8(a) => unsafe { bits(a.load(Ordering::Acquire)) },
+            Repr::U16(a) => unsafe { bits(a.load(Ordering::Acquire)) },
+            Repr::U32(a) => unsafe { bits(a.load(Ordering::Acquire)) },
+            #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+            Repr::U64(a) => unsafe { bits(a.load(Ordering::Acquire)) },
+            Repr::Locked(m) => *m.lock().unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+
+    /// Stores `value` (`Release` on the lock-free path).
+    #[inline]
+    pub fn store(&self, value: T) {
+        // SAFETY: as in `load`.
+        match &self.repr {
+            Repr::U8(a) => a.store(unsafe { bits(value) }, Ordering::Release),
+            Repr::U16(a) => a.store(unsafe { bits(value) }, Ordering::Release),
+            Repr::U32(a) => a.store(unsafe { bits(value) }, Ordering::Release),
+            #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+            Repr::U64(a) => a.store(unsafe { bits(value) }, Ordering::Release),
+            Repr::Locked(m) => *m.lock().unwrap_or_else(PoisonError::into_inner) = value,
+        }
+    }
+
+    /// Stores `value` and returns the previous value (`AcqRel` on the
+    /// lock-free path).
+    #[inline]
+    pub fn swap(&self, value: T) -> T {
+        // SAFETY: as in `load`.
+        match &self.repr {
+            Repr::U8(a) => unsafe { bits(a.swap(bits(value), Ordering::AcqRel)) },
+            Repr::U16(a) => unsafe { bits(a.swap(bits(value), Ordering::AcqRel)) },
+            Repr::U32(a) => unsafe { bits(a.swap(bits(value), Ordering::AcqRel)) },
+            #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+            Repr::U64(a) => unsafe { bits(a.swap(bits(value), Ordering::AcqRel)) },
+            Repr::Locked(m) => mem::replace(&mut *m.lock().unwrap_or_else(PoisonError::into_inner), value),
+        }
+    }
+
+    /// Consumes the cell, returning the stored value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.load()
+    }
+}
+
+impl<T: NoPadding + Default> Default for AtomicCell<T> {
+    fn default() -> Self {
+        AtomicCell::new(T::default())
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Debug for AtomicCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtomicCell")
+            .field("value", &self.load())
+            .field("lock_free", &!matches!(self.repr, Repr::Locked(_)))
+            .finish()
+    }
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+    #[test]
+    fn test_lock_free_pair() {
+        let cell = AtomicCell::new([1u32, 2]);
+        assert!(AtomicCell::<[u32; 2]>::is_lock_free());
+        assert!(matches!(cell.repr, Repr::U64(_)));
+
+        cell.store([3, 4]);
+        assert_eq!(cell.swap([5, 6]), [3, 4]);
+        assert_eq!(cell.into_inner(), [5, 6]);
+    }
+
+    #[test]
+    fn test_small_widths() {
+        let flag = AtomicCell::new(true);
+        assert!(flag.swap(false));
+        assert!(!flag.load());
+
+        let ch = AtomicCell::new('a');
+        ch.store('z');
+        assert_eq!(ch.load(), 'z');
+
+        let half = AtomicCell::new([7u8; 2]);
+        assert!(matches!(half.repr, Repr::U16(_)));
+        assert_eq!(half.load(), [7, 7]);
+    }
+
+    #[test]
+    fn test_oversized_is_locked() {
+        let cell = AtomicCell::new([1u64, 2, 3]);
+        assert!(!AtomicCell::<[u64; 3]>::is_lock_free());
+        assert!(matches!(cell.repr, Repr::Locked(_)));
+
+        cell.store([4, 5, 6]);
+        assert_eq!(cell.swap([7, 8, 9]), [4, 5, 6]);
+        assert_eq!(cell.load(), [7, 8, 9]);
+    }
+
+    #[test]
+    fn test_padded_type_is_locked() {
+        // `(u8, u16)` has a padding byte, so it must never be read as a `u32`.
+        let cell = AtomicCell::new_locked((1u8, 2u16));
+        assert!(matches!(cell.repr, Repr::Locked(_)));
+
+        cell.store((3, 4));
+        assert_eq!(cell.swap((5, 6)), (3, 4));
+        assert_eq!(cell.load(), (5, 6));
+    }
+
+    #[test]
+    fn test_concurrent_stores_never_tear() {
+        let cell = Arc::new(AtomicCell::new([0u32, 0]));
+        let writers: Vec<_> = (1..=4u32)
+            .map(|n| {
+                let cell = Arc::clone(&cell);
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        cell.store([n, n]);
+                    }
+                })
+            })
+            .collect();
+
+        for _ in 0..1000 {
+            let [a, b] = cell.load();
+            assert_eq!(a, b);
+        }
+        for writer in writers {
+            writer.join().unwrap();
+        }
+    }
+}
+
+#[cfg(all(test, feature = "loom"))]
+mod loom_tests {
+    use super::*;
+    use loom::sync::Arc;
+    use loom::thread;
+
+    /// Every stored value comes back out exactly once: either from one of the
+    /// two racing swaps or as the final value.
+    fn assert_no_lost_swap<T>(initial: T, a: T, b: T)
+    where
+        T: NoPadding + Ord + fmt::Debug + Send + Sync + 'static,
+    {
+        loom::model(move || {
+            let cell = Arc::new(AtomicCell::new(initial));
+            let other = Arc::clone(&cell);
+            let handle = thread::spawn(move || other.swap(a));
+            let mine = cell.swap(b);
+            let theirs = handle.join().unwrap();
+
+            let mut seen = [mine, theirs, cell.load()];
+            seen.sort();
+            let mut expected = [initial, a, b];
+            expected.sort();
+            assert_eq!(seen, expected);
+        });
+    }
+
+    #[test]
+    fn test_loom_lock_free_swaps() {
+        assert_no_lost_swap(0u32, 1, 2);
+    }
+
+    #[test]
+    fn test_loom_locked_swaps() {
+        assert_no_lost_swap([0u8; 3], [1; 3], [2; 3]);
+    }
+}
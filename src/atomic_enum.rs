@@ -0,0 +1,203 @@
+//! Fieldless enums stored in an `AtomicUsize`.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A fieldless enum that round-trips through a `usize` discriminant.
+///
+/// `from_repr(e.into_repr())` must return `Some(e)` for every value `e`.
+/// Values outside the enum's discriminants may map to `None`; they never
+/// reach [`AtomicEnum`] through its API.
+pub trait AtomicEnumRepr: Copy {
+    /// Returns the discriminant, usually `self as usize`.
+    fn into_repr(self) -> usize;
+
+    /// Returns the variant with discriminant `repr`, if there is one.
+    fn from_repr(repr: usize) -> Option<Self>;
+}
+
+/// An enum `E` with atomic `load`/`store`/`compare_exchange`.
+///
+/// The value lives in an `AtomicUsize` as `E::into_repr`, and every write
+/// goes through `E`, so the stored discriminant is always a valid one as
+/// long as [`AtomicEnumRepr`] round-trips. A `from_repr` that rejects a
+/// discriminant its own `into_repr` produced breaks that invariant, and
+/// reading such a value panics.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::{AtomicEnumRepr, WrapExt};
+///     use core::sync::atomic::Ordering;
+///
+///     #[derive(Clone, Copy, Debug, PartialEq)]
+///     enum Phase {
+///         Idle,
+///         Running,
+///     }
+///
+///     impl AtomicEnumRepr for Phase {
+///         fn into_repr(self) -> usize {
+///             self as usize
+///         }
+///
+///         fn from_repr(repr: usize) -> Option<Self> {
+///             match repr {
+///                 0 => Some(Phase::Idle),
+///                 1 => Some(Phase::Running),
+///                 _ => None,
+///             }
+///         }
+///     }
+///
+///     let phase = Phase::Idle.atomic_enum();
+///     let started = phase.compare_exchange(Phase::Idle, Phase::Running, Ordering::AcqRel, Ordering::Acquire);
+///     assert_eq!(started, Ok(Phase::Idle));
+///     assert_eq!(phase.load(Ordering::Acquire), Phase::Running);
+/// }
+/// ```
+pub struct AtomicEnum<E> {
+    repr: AtomicUsize,
+    _marker: PhantomData<E>,
+}
+
+impl<E: AtomicEnumRepr> AtomicEnum<E> {
+    /// Creates a new atomic enum.
+    #[inline]
+    pub fn new(value: E) -> Self {
+        AtomicEnum {
+            repr: AtomicUsize::new(value.into_repr()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Loads the current variant.
+    #[inline(always)]
+    pub fn load(&self, order: Ordering) -> E {
+        decode(self.repr.load(order))
+    }
+
+    /// Stores `value`.
+    #[inline(always)]
+    pub fn store(&self, value: E, order: Ordering) {
+        self.repr.store(value.into_repr(), order);
+    }
+
+    /// Stores `value` and returns the previous variant.
+    #[inline(always)]
+    pub fn swap(&self, value: E, order: Ordering) -> E {
+        decode(self.repr.swap(value.into_repr(), order))
+    }
+
+    /// Stores `new` if the current variant is `current`.
+    ///
+    /// Returns the previous variant: `Ok` if it was `current` and the store
+    /// happened, `Err` with the actual variant otherwise.
+    #[inline]
+    pub fn compare_exchange(&self, current: E, new: E, success: Ordering, failure: Ordering) -> Result<E, E> {
+        self.repr
+            .compare_exchange(current.into_repr(), new.into_repr(), success, failure)
+            .map(decode)
+            .map_err(decode)
+    }
+
+    /// Consumes the atomic, returning the variant.
+    #[inline(always)]
+    pub fn into_inner(self) -> E {
+        decode(self.repr.into_inner())
+    }
+}
+
+#[inline(always)]
+#[track_caller]
+fn decode<E: AtomicEnumRepr>(repr: usize) -> E {
+    match E::from_repr(repr) {
+        Some(value) => value,
+        None => panic!(
+            "AtomicEnum holds discriminant {}, which `{}::from_repr` rejects",
+            repr,
+            core::any::type_name::<E>()
+        ),
+    }
+}
+
+impl<E: AtomicEnumRepr + Default> Default for AtomicEnum<E> {
+    #[inline]
+    fn default() -> Self {
+        AtomicEnum::new(E::default())
+    }
+}
+
+impl<E: AtomicEnumRepr + fmt::Debug> fmt::Debug for AtomicEnum<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AtomicEnum").field(&self.load(Ordering::Relaxed)).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[repr(u8)]
+    enum State {
+        Idle = 0,
+        Running = 1,
+        Done = 2,
+    }
+
+    impl AtomicEnumRepr for State {
+        fn into_repr(self) -> usize {
+            self as usize
+        }
+
+        fn from_repr(repr: usize) -> Option<Self> {
+            match repr {
+                0 => Some(State::Idle),
+                1 => Some(State::Running),
+                2 => Some(State::Done),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_cas_transitions() {
+        let state = AtomicEnum::new(State::Idle);
+        assert_eq!(
+            state.compare_exchange(State::Idle, State::Running, Ordering::AcqRel, Ordering::Acquire),
+            Ok(State::Idle)
+        );
+        assert_eq!(
+            state.compare_exchange(State::Idle, State::Done, Ordering::AcqRel, Ordering::Acquire),
+            Err(State::Running)
+        );
+        assert_eq!(state.swap(State::Done, Ordering::AcqRel), State::Running);
+        assert_eq!(format!("{:?}", state), "AtomicEnum(Done)");
+        assert_eq!(state.into_inner(), State::Done);
+    }
+
+    #[test]
+    fn test_only_one_thread_wins_transition() {
+        let state = AtomicEnum::new(State::Idle);
+        let winners: usize = thread::scope(|s| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    s.spawn(|| {
+                        state
+                            .compare_exchange(State::Idle, State::Running, Ordering::AcqRel, Ordering::Acquire)
+                            .is_ok() as usize
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).sum()
+        });
+
+        assert_eq!(winners, 1);
+        assert_eq!(state.load(Ordering::Acquire), State::Running);
+    }
+}
@@ -0,0 +1,229 @@
+//! `Duration` and `Instant` storage on top of `AtomicU64`.
+
+use std::fmt;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use crate::wide_atomic::AtomicU64;
+
+#[inline(always)]
+fn to_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// A `Duration` stored as whole nanoseconds in an `AtomicU64`.
+///
+/// Durations longer than `u64::MAX` nanoseconds (about 584 years) saturate to
+/// that maximum on every store or add, rather than wrapping or panicking.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::AtomicDuration;
+///     use core::sync::atomic::Ordering;
+///     use std::time::Duration;
+///
+///     let total = AtomicDuration::new(Duration::from_millis(5));
+///     total.fetch_add_duration(Duration::from_millis(10), Ordering::Relaxed);
+///     assert_eq!(total.load(Ordering::Relaxed), Duration::from_millis(15));
+/// }
+/// ```
+#[derive(Default)]
+pub struct AtomicDuration {
+    nanos: AtomicU64,
+}
+
+impl AtomicDuration {
+    /// Creates a new atomic duration, saturating values beyond `u64::MAX` nanos.
+    #[inline]
+    pub fn new(duration: Duration) -> Self {
+        AtomicDuration {
+            nanos: AtomicU64::new(to_nanos(duration)),
+        }
+    }
+
+    /// Loads the stored duration.
+    #[inline(always)]
+    pub fn load(&self, order: Ordering) -> Duration {
+        Duration::from_nanos(self.nanos.load(order))
+    }
+
+    /// Stores `duration`, saturating values beyond `u64::MAX` nanos.
+    #[inline(always)]
+    pub fn store(&self, duration: Duration, order: Ordering) {
+        self.nanos.store(to_nanos(duration), order);
+    }
+
+    /// Stores `duration` and returns the previous value.
+    #[inline(always)]
+    pub fn swap(&self, duration: Duration, order: Ordering) -> Duration {
+        Duration::from_nanos(self.nanos.swap(to_nanos(duration), order))
+    }
+
+    /// Adds `duration` to the stored value, saturating at `u64::MAX` nanos,
+    /// and returns the previous value.
+    pub fn fetch_add_duration(&self, duration: Duration, order: Ordering) -> Duration {
+        let delta = to_nanos(duration);
+        let previous = self
+            .nanos
+            .fetch_update(order, Ordering::Relaxed, |nanos| {
+                Some(nanos.saturating_add(delta))
+            })
+            .unwrap_or_else(|nanos| nanos);
+        Duration::from_nanos(previous)
+    }
+
+    /// Consumes the atomic, returning the stored duration.
+    #[inline(always)]
+    pub fn into_inner(self) -> Duration {
+        Duration::from_nanos(self.nanos.into_inner())
+    }
+}
+
+impl fmt::Debug for AtomicDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(Ordering::Relaxed), f)
+    }
+}
+
+/// An `Instant` stored as a nanosecond offset from a fixed base instant.
+///
+/// The base is the instant passed to [`new`](AtomicInstant::new). Storing an
+/// instant earlier than the base saturates to the base, and offsets beyond
+/// `u64::MAX` nanoseconds saturate to that maximum.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::AtomicInstant;
+///     use core::sync::atomic::Ordering;
+///     use std::time::{Duration, Instant};
+///
+///     let start = Instant::now();
+///     let last_seen = AtomicInstant::new(start);
+///     last_seen.store(start + Duration::from_secs(1), Ordering::Relaxed);
+///     assert_eq!(last_seen.load(Ordering::Relaxed) - start, Duration::from_secs(1));
+/// }
+/// ```
+pub struct AtomicInstant {
+    base: Instant,
+    offset: AtomicU64,
+}
+
+impl AtomicInstant {
+    /// Creates a new atomic instant holding `instant`, which also becomes the
+    /// base for later stores.
+    #[inline]
+    pub fn new(instant: Instant) -> Self {
+        AtomicInstant {
+            base: instant,
+            offset: AtomicU64::new(0),
+        }
+    }
+
+    /// Creates a new atomic instant holding `Instant::now()`.
+    #[inline]
+    pub fn now() -> Self {
+        AtomicInstant::new(Instant::now())
+    }
+
+    /// Loads the stored instant.
+    #[inline(always)]
+    pub fn load(&self, order: Ordering) -> Instant {
+        self.base + Duration::from_nanos(self.offset.load(order))
+    }
+
+    /// Stores `instant`, saturating to the base if it is earlier.
+    #[inline(always)]
+    pub fn store(&self, instant: Instant, order: Ordering) {
+        self.offset.store(self.offset_of(instant), order);
+    }
+
+    /// Stores `Instant::now()`.
+    #[inline(always)]
+    pub fn touch(&self, order: Ordering) {
+        self.store(Instant::now(), order);
+    }
+
+    /// Returns the time elapsed since the stored instant.
+    #[inline]
+    pub fn elapsed(&self, order: Ordering) -> Duration {
+        self.load(order).elapsed()
+    }
+
+    #[inline(always)]
+    fn offset_of(&self, instant: Instant) -> u64 {
+        to_nanos(instant.saturating_duration_since(self.base))
+    }
+}
+
+impl fmt::Debug for AtomicInstant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(Ordering::Relaxed), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_duration_round_trip() {
+        let duration = Duration::new(3, 141_592_653);
+        let atomic = AtomicDuration::new(Duration::ZERO);
+        atomic.store(duration, Ordering::Relaxed);
+        assert_eq!(atomic.load(Ordering::Relaxed), duration);
+        assert_eq!(atomic.swap(Duration::ZERO, Ordering::Relaxed), duration);
+    }
+
+    #[test]
+    fn test_duration_saturates() {
+        let atomic = AtomicDuration::new(Duration::MAX);
+        assert_eq!(atomic.load(Ordering::Relaxed), Duration::from_nanos(u64::MAX));
+
+        atomic.store(Duration::from_nanos(u64::MAX - 1), Ordering::Relaxed);
+        atomic.fetch_add_duration(Duration::from_secs(1), Ordering::Relaxed);
+        assert_eq!(atomic.into_inner(), Duration::from_nanos(u64::MAX));
+    }
+
+    #[test]
+    fn test_fetch_add_across_threads() {
+        let total = Arc::new(AtomicDuration::default());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let total = Arc::clone(&total);
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        total.fetch_add_duration(Duration::from_micros(1), Ordering::Relaxed);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(total.load(Ordering::Relaxed), Duration::from_millis(4));
+    }
+
+    #[test]
+    fn test_instant_store_and_saturate() {
+        let base = Instant::now();
+        let later = base + Duration::from_millis(250);
+        let atomic = AtomicInstant::new(later);
+        assert_eq!(atomic.load(Ordering::Relaxed), later);
+
+        // Earlier than the base clamps to the base.
+        atomic.store(base, Ordering::Relaxed);
+        assert_eq!(atomic.load(Ordering::Relaxed), later);
+
+        atomic.store(later + Duration::from_secs(2), Ordering::Relaxed);
+        assert_eq!(atomic.load(Ordering::Relaxed) - later, Duration::from_secs(2));
+    }
+}
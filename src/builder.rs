@@ -0,0 +1,130 @@
+//! A `RefCell`-backed accumulator for builders used behind shared references.
+
+use core::cell::RefCell;
+use core::fmt;
+
+/// Accumulates mutations through `&self` and yields the final value on
+/// [`build`](Builder::build).
+///
+/// Every step takes `&self` and returns `&Self`, so a builder can be threaded
+/// through code that only holds a shared reference to it.
+///
+/// # Panics
+///
+/// Calling [`with`](Builder::with) re-entrantly from inside another `with`
+/// closure panics, as the underlying `RefCell` is already mutably borrowed.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::Builder;
+///
+///     let builder = Builder::new(Vec::new());
+///     builder.with(|v| v.push(1)).with(|v| v.push(2));
+///     assert_eq!(builder.build(), vec![1, 2]);
+/// }
+/// ```
+#[derive(Default)]
+pub struct Builder<T> {
+    value: RefCell<T>,
+}
+
+impl<T> Builder<T> {
+    /// Creates a builder starting from `value`.
+    #[inline(always)]
+    pub fn new(value: T) -> Self {
+        Builder {
+            value: RefCell::new(value),
+        }
+    }
+
+    /// Applies `f` to the value being built.
+    #[inline]
+    pub fn with<F>(&self, f: F) -> &Self
+    where
+        F: FnOnce(&mut T),
+    {
+        f(&mut self.value.borrow_mut());
+        self
+    }
+
+    /// Replaces the value being built.
+    #[inline]
+    pub fn set(&self, value: T) -> &Self {
+        *self.value.borrow_mut() = value;
+        self
+    }
+
+    /// Runs `f` against the current state without modifying it.
+    #[inline]
+    pub fn inspect<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        f(&self.value.borrow())
+    }
+
+    /// Consumes the builder, returning the accumulated value.
+    #[inline(always)]
+    pub fn build(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T> From<T> for Builder<T> {
+    #[inline(always)]
+    fn from(value: T) -> Self {
+        Builder::new(value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Builder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder").field("value", &self.value).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Request {
+        method: String,
+        headers: Vec<(String, String)>,
+        retries: u32,
+    }
+
+    fn add_auth(builder: &Builder<Request>) {
+        builder.with(|r| r.headers.push(("Authorization".into(), "token".into())));
+    }
+
+    #[test]
+    fn test_builder_chaining() {
+        let builder = Builder::<Request>::default();
+        builder
+            .with(|r| r.method = "GET".into())
+            .with(|r| r.retries += 2)
+            .with(|r| r.retries += 1);
+        add_auth(&builder);
+
+        assert_eq!(builder.inspect(|r| r.headers.len()), 1);
+        assert_eq!(
+            builder.build(),
+            Request {
+                method: "GET".into(),
+                headers: vec![("Authorization".into(), "token".into())],
+                retries: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_builder_set() {
+        let builder = Builder::from(1u32);
+        builder.set(10).with(|v| *v *= 2);
+        assert_eq!(builder.build(), 20);
+    }
+}
@@ -0,0 +1,109 @@
+//! Identity-based `Eq`/`Hash`/`Ord` for pointer types.
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+/// Wraps a pointer (`Arc<T>`, `Rc<T>`, `&T`, `Box<T>`, ...) so that
+/// comparisons and hashing use the address it points to, not the value.
+///
+/// Two `ByAddress` are equal exactly when they point at the same object,
+/// whatever `T`'s own `PartialEq` says, so shared nodes can be deduplicated
+/// in a `HashSet` or used as `HashMap` keys even if `T` isn't `Hash`. For
+/// unsized targets only the data address is compared; slice lengths and
+/// vtables are ignored.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::ByAddress;
+///     use std::collections::HashSet;
+///     use std::rc::Rc;
+///
+///     let a = Rc::new(String::from("node"));
+///     let b = Rc::new(String::from("node"));
+///     let seen: HashSet<_> = [&a, &a, &b].into_iter().cloned().map(ByAddress).collect();
+///     assert_eq!(seen.len(), 2);
+/// }
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct ByAddress<P>(pub P);
+
+impl<P: Deref> ByAddress<P> {
+    #[inline(always)]
+    fn addr(&self) -> *const u8 {
+        &*self.0 as *const P::Target as *const u8
+    }
+
+    /// Returns the wrapped pointer.
+    #[inline(always)]
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<P: Deref> PartialEq for ByAddress<P> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.addr() == other.addr()
+    }
+}
+
+impl<P: Deref> Eq for ByAddress<P> {}
+
+impl<P: Deref> Hash for ByAddress<P> {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr().hash(state)
+    }
+}
+
+impl<P: Deref> PartialOrd for ByAddress<P> {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: Deref> Ord for ByAddress<P> {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.addr().cmp(&other.addr())
+    }
+}
+
+impl<P> Deref for ByAddress<P> {
+    type Target = P;
+
+    #[inline(always)]
+    fn deref(&self) -> &P {
+        &self.0
+    }
+}
+
+impl<P: fmt::Debug> fmt::Debug for ByAddress<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ByAddress").field(&self.0).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equality_is_by_address() {
+        let (a, b) = (5u32, 5u32);
+        assert!(ByAddress(&a) == ByAddress(&a));
+        assert!(ByAddress(&a) != ByAddress(&b));
+
+        let items = [1u8, 2, 3];
+        let whole: &[u8] = &items;
+        let prefix: &[u8] = &items[..1];
+        // Only the data address counts, not the slice length.
+        assert!(ByAddress(whole) == ByAddress(prefix));
+    }
+}
@@ -0,0 +1,203 @@
+//! A `Weak<T>` that keeps its last successful upgrade around.
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+
+/// A `sync::Weak<T>` plus a cached `Arc<T>` from the last upgrade.
+///
+/// `Weak::upgrade` is an atomic read-modify-write on the strong count, and
+/// dropping the resulting `Arc` is another. In a loop that upgrades on every
+/// iteration, [`get`](CachedWeak::get) replaces both with two atomic loads:
+/// it returns the cached `Arc` as long as something else still holds a
+/// strong reference too.
+///
+/// The cache is itself a strong reference, so while it is filled the value
+/// stays alive. Once the caches are the *only* strong references left, the
+/// next `get` treats them as stale: it drops its cache, which frees the value
+/// once every handle has done so, and returns `None`, just as a plain `Weak`
+/// would. Call [`clear`](CachedWeak::clear) to let go of the value between
+/// bursts of use without waiting for that.
+///
+/// Clones of a handle share a count of how many of them have a filled
+/// cache, so their caches aren't mistaken for outside references to each
+/// other. Handles made by separate [`new`](CachedWeak::new) calls don't
+/// share that count; clone one handle rather than creating a second for
+/// the same value.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::CachedWeak;
+///     use std::sync::Arc;
+///
+///     let session = Arc::new(String::from("alive"));
+///     let mut handle = CachedWeak::new(&session);
+///     for _ in 0..3 {
+///         assert_eq!(handle.get().map(|s| s.len()), Some(5));
+///     }
+///
+///     drop(session);
+///     assert!(handle.get().is_none());
+/// }
+/// ```
+pub struct CachedWeak<T: ?Sized> {
+    weak: Weak<T>,
+    cached: Option<Arc<T>>,
+    // Filled caches among this handle and its clones. Raised before an
+    // upgrade and lowered after a cache is dropped, so it never falls below
+    // the number of strong references the caches actually hold.
+    caches: Arc<AtomicUsize>,
+}
+
+impl<T: ?Sized> CachedWeak<T> {
+    /// Creates a handle to `arc` without caching it yet.
+    #[inline]
+    pub fn new(arc: &Arc<T>) -> Self {
+        CachedWeak::from(Arc::downgrade(arc))
+    }
+
+    /// Returns a strong reference to the value, or `None` once every strong
+    /// reference other than this handle's cache has been dropped.
+    pub fn get(&mut self) -> Option<&Arc<T>> {
+        if !self.is_fresh() {
+            // Drop the stale cache first, so a value only this cache kept
+            // alive is freed and the upgrade below fails.
+            self.clear();
+            self.caches.fetch_add(1, Ordering::SeqCst);
+            match self.weak.upgrade() {
+                Some(arc) => {
+                    self.cached = Some(arc);
+                    // The upgrade may have succeeded only because a clone's
+                    // cache still holds the value.
+                    if !self.is_fresh() {
+                        self.clear();
+                    }
+                }
+                None => {
+                    self.caches.fetch_sub(1, Ordering::SeqCst);
+                }
+            }
+        }
+        self.cached.as_ref()
+    }
+
+    /// Whether the cache is filled and some strong reference other than the
+    /// caches of this handle and its clones exists.
+    #[inline]
+    fn is_fresh(&self) -> bool {
+        match &self.cached {
+            Some(arc) => Arc::strong_count(arc) > self.caches.load(Ordering::SeqCst),
+            None => false,
+        }
+    }
+
+    /// Drops the cached `Arc`, so this handle no longer keeps the value
+    /// alive.
+    #[inline]
+    pub fn clear(&mut self) {
+        if let Some(arc) = self.cached.take() {
+            drop(arc);
+            self.caches.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Returns the underlying weak reference.
+    #[inline(always)]
+    pub fn weak(&self) -> &Weak<T> {
+        &self.weak
+    }
+}
+
+impl<T: ?Sized> From<Weak<T>> for CachedWeak<T> {
+    #[inline]
+    fn from(weak: Weak<T>) -> Self {
+        CachedWeak {
+            weak,
+            cached: None,
+            caches: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+impl<T: ?Sized> Clone for CachedWeak<T> {
+    /// Clones the weak reference and shares the cache count; the clone
+    /// starts with an empty cache of its own.
+    #[inline]
+    fn clone(&self) -> Self {
+        CachedWeak {
+            weak: Weak::clone(&self.weak),
+            cached: None,
+            caches: Arc::clone(&self.caches),
+        }
+    }
+}
+
+impl<T: ?Sized> Drop for CachedWeak<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for CachedWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedWeak")
+            .field("cached", &self.cached.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_get_reuses_cached_arc() {
+        let value = Arc::new(vec![1u8, 2, 3]);
+        let mut handle = CachedWeak::new(&value);
+        assert_eq!(Arc::strong_count(&value), 1);
+
+        for _ in 0..100 {
+            assert_eq!(handle.get().unwrap().len(), 3);
+            // The external `Arc` plus the one cached handle, never more.
+            assert_eq!(Arc::strong_count(&value), 2);
+        }
+
+        handle.clear();
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_get_returns_none_after_last_external_drop() {
+        let value = Arc::new(String::from("v"));
+        let mut handle = CachedWeak::new(&value);
+        assert!(handle.get().is_some());
+
+        drop(value);
+        // The cache alone keeps the value alive until the next `get`.
+        assert_eq!(handle.weak().strong_count(), 1);
+        assert!(handle.get().is_none());
+        assert_eq!(handle.weak().strong_count(), 0);
+        assert!(handle.get().is_none());
+    }
+
+    #[test]
+    fn test_clones_do_not_keep_each_other_alive() {
+        let value = Arc::new(5u32);
+        let mut a = CachedWeak::new(&value);
+        assert!(a.get().is_some());
+        let mut b = a.clone();
+        assert!(b.get().is_some());
+        assert_eq!(Arc::strong_count(&value), 3);
+        // Both caches are still fresh while the external `Arc` lives.
+        assert!(a.get().is_some() && b.get().is_some());
+
+        drop(value);
+        assert!(a.get().is_none());
+        assert!(b.get().is_none());
+        assert_eq!(a.weak().strong_count(), 0);
+    }
+}
@@ -0,0 +1,133 @@
+//! A single-threaded cached value keyed by a dependency version.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+
+/// A value derived by a closure and cached until its dependencies change.
+///
+/// Callers pass the current version of whatever the value depends on to
+/// [`get`](Computed::get), typically a counter bumped on every write to the
+/// inputs. The closure only reruns when that version differs from the one
+/// the cache was computed at; otherwise a clone of the cached value is
+/// returned.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::Computed;
+///     use std::cell::Cell;
+///     use std::rc::Rc;
+///
+///     let items = Rc::new(Cell::new(3u32));
+///     let inputs = Rc::clone(&items);
+///     let total = Computed::new(move || inputs.get() * 10);
+///
+///     assert_eq!(total.get(1), 30);
+///     items.set(4);
+///     assert_eq!(total.get(1), 30); // same version: cached
+///     assert_eq!(total.get(2), 40); // new version: recomputed
+///     assert_eq!(total.recomputations(), 2);
+/// }
+/// ```
+pub struct Computed<T: Clone, F = Box<dyn Fn() -> T>> {
+    recomputations: Cell<u64>,
+    cache: RefCell<Option<(u64, T)>>,
+    compute: F,
+}
+
+impl<T: Clone, F: Fn() -> T> Computed<T, F> {
+    /// Creates a property that is computed on the first [`get`](Computed::get).
+    #[inline(always)]
+    pub fn new(compute: F) -> Self {
+        Computed {
+            recomputations: Cell::new(0),
+            cache: RefCell::new(None),
+            compute,
+        }
+    }
+
+    /// Returns the value for dependency `version`, recomputing it if the cache
+    /// was computed at a different version.
+    pub fn get(&self, version: u64) -> T {
+        if let Some((cached_at, value)) = &*self.cache.borrow() {
+            if *cached_at == version {
+                return value.clone();
+            }
+        }
+        // Compute without holding the borrow so the closure may read other
+        // computed values, or even this one at the cached version.
+        let value = (self.compute)();
+        self.recomputations.set(self.recomputations.get() + 1);
+        *self.cache.borrow_mut() = Some((version, value.clone()));
+        value
+    }
+
+    /// Drops the cached value so the next [`get`](Computed::get) recomputes.
+    #[inline]
+    pub fn invalidate(&self) {
+        self.cache.borrow_mut().take();
+    }
+
+    /// Returns the version the cached value was computed at, if any.
+    #[inline]
+    pub fn cached_version(&self) -> Option<u64> {
+        self.cache.borrow().as_ref().map(|(version, _)| *version)
+    }
+
+    /// Returns how many times the closure has run.
+    #[inline(always)]
+    pub fn recomputations(&self) -> u64 {
+        self.recomputations.get()
+    }
+}
+
+impl<T: Clone + fmt::Debug, F> fmt::Debug for Computed<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Computed")
+            .field("cache", &self.cache)
+            .field("recomputations", &self.recomputations.get())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_recomputes_only_on_version_change() {
+        let source = Rc::new(RefCell::new(vec![1, 2, 3]));
+        let input = Rc::clone(&source);
+        let sum = Computed::new(move || input.borrow().iter().sum::<i32>());
+
+        assert_eq!(sum.get(0), 6);
+        assert_eq!(sum.get(0), 6);
+        assert_eq!(sum.recomputations(), 1);
+
+        source.borrow_mut().push(4);
+        assert_eq!(sum.get(0), 6);
+        assert_eq!(sum.get(1), 10);
+        assert_eq!(sum.get(1), 10);
+        assert_eq!(sum.recomputations(), 2);
+        assert_eq!(sum.cached_version(), Some(1));
+    }
+
+    #[test]
+    fn test_invalidate_forces_recompute() {
+        let runs = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&runs);
+        let value: Computed<String> = Computed::new(Box::new(move || {
+            counter.set(counter.get() + 1);
+            format!("run {}", counter.get())
+        }));
+
+        assert_eq!(value.get(7), "run 1");
+        value.invalidate();
+        assert_eq!(value.cached_version(), None);
+        assert_eq!(value.get(7), "run 2");
+        assert_eq!(runs.get(), 2);
+    }
+}
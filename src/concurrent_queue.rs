@@ -0,0 +1,193 @@
+//! A shared FIFO work queue over `Mutex<VecDeque<T>>` and `Condvar`.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+
+use crate::MutexExt;
+
+/// A multi-producer, multi-consumer FIFO queue shared between threads.
+///
+/// Clones are handles to the same queue, so a clone can be moved into each
+/// producer and worker. [`pop_front`](ConcurrentQueue::pop_front) returns
+/// `None` right away on an empty queue, while
+/// [`pop_front_blocking`](ConcurrentQueue::pop_front_blocking) parks the
+/// thread until a [`push_back`](ConcurrentQueue::push_back) hands it an item.
+/// Every lock is held only for the `VecDeque` operation itself.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::ConcurrentQueue;
+///     use std::thread;
+///
+///     let jobs = ConcurrentQueue::with_capacity(16);
+///     let worker = {
+///         let jobs = jobs.clone();
+///         thread::spawn(move || jobs.pop_front_blocking() * 2)
+///     };
+///
+///     jobs.push_back(21);
+///     assert_eq!(worker.join().unwrap(), 42);
+///     assert!(jobs.is_empty());
+/// }
+/// ```
+pub struct ConcurrentQueue<T> {
+    state: Arc<(Mutex<VecDeque<T>>, Condvar)>,
+}
+
+impl<T> ConcurrentQueue<T> {
+    /// Creates an empty queue.
+    #[inline]
+    pub fn new() -> Self {
+        ConcurrentQueue::from(VecDeque::new())
+    }
+
+    /// Creates an empty queue with room for `capacity` items before it
+    /// reallocates.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        ConcurrentQueue::from(VecDeque::with_capacity(capacity))
+    }
+
+    /// Appends an item, waking one thread blocked in
+    /// [`pop_front_blocking`](ConcurrentQueue::pop_front_blocking).
+    pub fn push_back(&self, item: T) {
+        let (items, ready) = &*self.state;
+        items.lock_ignore_poison().push_back(item);
+        ready.notify_one();
+    }
+
+    /// Removes the oldest item, or returns `None` if the queue is empty.
+    #[inline]
+    pub fn pop_front(&self) -> Option<T> {
+        self.state.0.lock_ignore_poison().pop_front()
+    }
+
+    /// Removes the oldest item, blocking until one is pushed if the queue is
+    /// empty.
+    pub fn pop_front_blocking(&self) -> T {
+        let (items, ready) = &*self.state;
+        let mut items = ready
+            .wait_while(items.lock_ignore_poison(), |items| items.is_empty())
+            .unwrap_or_else(PoisonError::into_inner);
+        match items.pop_front() {
+            Some(item) => item,
+            None => unreachable!("woke with an empty queue"),
+        }
+    }
+
+    /// Returns the number of queued items.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.state.0.lock_ignore_poison().len()
+    }
+
+    /// Returns `true` if no items are queued.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.state.0.lock_ignore_poison().is_empty()
+    }
+}
+
+impl<T> From<VecDeque<T>> for ConcurrentQueue<T> {
+    /// Creates a queue whose front is the front of `items`.
+    #[inline]
+    fn from(items: VecDeque<T>) -> Self {
+        ConcurrentQueue {
+            state: Arc::new((Mutex::new(items), Condvar::new())),
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for ConcurrentQueue<T> {
+    /// Creates a queue whose front is the first element of `items`, reusing
+    /// its buffer.
+    #[inline]
+    fn from(items: Vec<T>) -> Self {
+        ConcurrentQueue::from(VecDeque::from(items))
+    }
+}
+
+impl<T> Clone for ConcurrentQueue<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ConcurrentQueue {
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<T> Default for ConcurrentQueue<T> {
+    #[inline]
+    fn default() -> Self {
+        ConcurrentQueue::new()
+    }
+}
+
+impl<T> fmt::Debug for ConcurrentQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcurrentQueue")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_consumer_blocks_until_producer_pushes() {
+        let queue = ConcurrentQueue::new();
+        let consumer = {
+            let queue = queue.clone();
+            thread::spawn(move || (0..3).map(|_| queue.pop_front_blocking()).collect::<Vec<_>>())
+        };
+        thread::sleep(Duration::from_millis(10));
+        assert!(!consumer.is_finished());
+
+        for n in 1..=3 {
+            queue.push_back(n);
+        }
+        assert_eq!(consumer.join().unwrap(), [1, 2, 3]);
+        assert_eq!(queue.pop_front(), None);
+    }
+
+    #[test]
+    fn test_fifo_across_producers() {
+        let queue = ConcurrentQueue::with_capacity(400);
+        thread::scope(|s| {
+            for producer in 0..4 {
+                let queue = &queue;
+                s.spawn(move || {
+                    for n in 0..100 {
+                        queue.push_back((producer, n));
+                    }
+                });
+            }
+        });
+        assert_eq!(queue.len(), 400);
+
+        // Each producer's items come out in the order it pushed them.
+        let mut next = [0; 4];
+        while let Some((producer, n)) = queue.pop_front() {
+            assert_eq!(n, next[producer]);
+            next[producer] += 1;
+        }
+        assert_eq!(next, [100; 4]);
+    }
+
+    #[test]
+    fn test_from_vec_keeps_order() {
+        let queue = ConcurrentQueue::from(vec!["a", "b"]);
+        queue.push_back("c");
+        assert_eq!(format!("{:?}", queue), "ConcurrentQueue { len: 3 }");
+        assert_eq!(queue.pop_front(), Some("a"));
+        assert_eq!(queue.pop_front_blocking(), "b");
+    }
+}
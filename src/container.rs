@@ -0,0 +1,155 @@
+//! A minimal single-threaded dependency container keyed by type.
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+type Factory<T> = Box<dyn FnOnce(&Container) -> T>;
+
+/// Stores at most one value per type and hands out shared `Rc` handles.
+///
+/// Values can be registered eagerly with [`register`](Container::register),
+/// or deferred with [`register_lazy`](Container::register_lazy), in which case
+/// the factory runs on the first [`resolve`](Container::resolve) and may
+/// itself resolve other dependencies from the container.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::Container;
+///
+///     struct Config { url: &'static str }
+///     struct Client { url: String }
+///
+///     let container = Container::new();
+///     container.register(Config { url: "db://local" });
+///     container.register_lazy(|c: &Container| Client {
+///         url: c.resolve::<Config>().unwrap().url.to_string(),
+///     });
+///
+///     assert_eq!(container.resolve::<Client>().unwrap().url, "db://local");
+/// }
+/// ```
+#[derive(Default)]
+pub struct Container {
+    entries: RefCell<HashMap<TypeId, Box<dyn Any>>>,
+}
+
+impl Container {
+    /// Creates an empty container.
+    #[inline]
+    pub fn new() -> Self {
+        Container::default()
+    }
+
+    /// Registers `value` as the instance of `T`, replacing any previous one.
+    pub fn register<T: 'static>(&self, value: T) {
+        self.entries
+            .borrow_mut()
+            .insert(TypeId::of::<T>(), Box::new(Rc::new(value)));
+    }
+
+    /// Registers a factory that builds the instance of `T` on first use.
+    pub fn register_lazy<T, F>(&self, factory: F)
+    where
+        T: 'static,
+        F: FnOnce(&Container) -> T + 'static,
+    {
+        let factory: Factory<T> = Box::new(factory);
+        self.entries
+            .borrow_mut()
+            .insert(TypeId::of::<T>(), Box::new(factory));
+    }
+
+    /// Returns the registered instance of `T`, running its factory if needed.
+    ///
+    /// Returns `None` if nothing was registered for `T`, or if `T`'s own
+    /// factory tries to resolve `T` (a dependency cycle).
+    pub fn resolve<T: 'static>(&self) -> Option<Rc<T>> {
+        let id = TypeId::of::<T>();
+        let factory = {
+            let mut entries = self.entries.borrow_mut();
+            if let Some(instance) = entries.get(&id)?.downcast_ref::<Rc<T>>() {
+                return Some(Rc::clone(instance));
+            }
+            entries.remove(&id)?.downcast::<Factory<T>>().ok()?
+        };
+
+        // The borrow is released so the factory can resolve its own dependencies.
+        let instance = Rc::new(factory(self));
+        self.entries
+            .borrow_mut()
+            .insert(id, Box::new(Rc::clone(&instance)));
+        Some(instance)
+    }
+
+    /// Returns `true` if an instance or factory is registered for `T`.
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.entries.borrow().contains_key(&TypeId::of::<T>())
+    }
+}
+
+impl fmt::Debug for Container {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Container")
+            .field("len", &self.entries.borrow().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[derive(Debug, PartialEq)]
+    struct Port(u16);
+
+    #[derive(Debug, PartialEq)]
+    struct Host(String);
+
+    #[test]
+    fn test_register_and_resolve() {
+        let container = Container::new();
+        container.register(Port(8080));
+        container.register(Host("localhost".into()));
+
+        assert_eq!(*container.resolve::<Port>().unwrap(), Port(8080));
+        assert_eq!(*container.resolve::<Host>().unwrap(), Host("localhost".into()));
+        assert!(container.resolve::<u32>().is_none());
+
+        let a = container.resolve::<Port>().unwrap();
+        let b = container.resolve::<Port>().unwrap();
+        assert!(Rc::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    fn test_lazy_runs_once() {
+        let calls = Rc::new(Cell::new(0));
+        let container = Container::new();
+        container.register(Port(1));
+
+        let counter = Rc::clone(&calls);
+        container.register_lazy(move |c: &Container| {
+            counter.set(counter.get() + 1);
+            Host(format!("node:{}", c.resolve::<Port>().unwrap().0))
+        });
+        assert!(container.contains::<Host>());
+        assert_eq!(calls.get(), 0);
+
+        assert_eq!(*container.resolve::<Host>().unwrap(), Host("node:1".into()));
+        assert_eq!(*container.resolve::<Host>().unwrap(), Host("node:1".into()));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_self_dependency_is_none() {
+        let container = Container::new();
+        container.register_lazy(|c: &Container| Port(c.resolve::<Port>().map_or(0, |p| p.0 + 1)));
+        assert_eq!(*container.resolve::<Port>().unwrap(), Port(0));
+    }
+}
@@ -0,0 +1,180 @@
+//! A `RefCell`-backed stack of scoped context values.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+
+/// A stack of context values where each scope overrides its parent until the
+/// scope ends.
+///
+/// [`push_scope`](ContextStack::push_scope) returns a [`ScopeGuard`] that pops
+/// the value again on drop, so the innermost live scope is always what
+/// [`current`](ContextStack::current) reports. Pair it with `thread_local!` for
+/// dynamically scoped, per-thread context such as request IDs.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::ContextStack;
+///
+///     thread_local! {
+///         static REQUEST_ID: ContextStack<u64> = const { ContextStack::new() };
+///     }
+///
+///     REQUEST_ID.with(|ids| {
+///         let _outer = ids.push_scope(1);
+///         {
+///             let _inner = ids.push_scope(2);
+///             assert_eq!(ids.current(), Some(2));
+///         }
+///         assert_eq!(ids.current(), Some(1));
+///     });
+/// }
+/// ```
+#[derive(Default)]
+pub struct ContextStack<T> {
+    // Each scope is tagged with the generation it was pushed under, so a
+    // guard whose scope was already ended by an outer one can tell.
+    scopes: RefCell<Vec<(u64, T)>>,
+    next_generation: Cell<u64>,
+}
+
+impl<T> ContextStack<T> {
+    /// Creates an empty stack.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        ContextStack {
+            scopes: RefCell::new(Vec::new()),
+            next_generation: Cell::new(0),
+        }
+    }
+
+    /// Enters a scope in which `value` is the current context.
+    ///
+    /// The scope ends when the returned guard is dropped. Dropping an outer
+    /// guard first also ends every scope nested inside it, and the nested
+    /// guards then do nothing when they drop.
+    pub fn push_scope(&self, value: T) -> ScopeGuard<'_, T> {
+        let generation = self.next_generation.get();
+        self.next_generation.set(generation.wrapping_add(1));
+        let mut scopes = self.scopes.borrow_mut();
+        let depth = scopes.len();
+        scopes.push((generation, value));
+        ScopeGuard {
+            stack: self,
+            depth,
+            generation,
+        }
+    }
+
+    /// Returns the number of active scopes.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.scopes.borrow().len()
+    }
+}
+
+impl<T: Clone> ContextStack<T> {
+    /// Returns a clone of the innermost value, or `None` outside any scope.
+    #[inline]
+    pub fn current(&self) -> Option<T> {
+        self.scopes.borrow().last().map(|(_, value)| value.clone())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ContextStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.scopes.try_borrow() {
+            Ok(scopes) => {
+                let values: Vec<&T> = scopes.iter().map(|(_, value)| value).collect();
+                f.debug_tuple("ContextStack").field(&values).finish()
+            }
+            Err(_) => f
+                .debug_tuple("ContextStack")
+                .field(&format_args!("<borrowed>"))
+                .finish(),
+        }
+    }
+}
+
+/// Guard returned by [`ContextStack::push_scope`]; ends the scope on drop.
+#[must_use = "the scope ends as soon as the guard is dropped"]
+pub struct ScopeGuard<'a, T> {
+    stack: &'a ContextStack<T>,
+    depth: usize,
+    generation: u64,
+}
+
+impl<T> Drop for ScopeGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut scopes = self.stack.scopes.borrow_mut();
+        // If an outer guard already ended this scope, whatever now sits at
+        // `depth` belongs to a later push and must be left alone.
+        if scopes.get(self.depth).map(|(generation, _)| *generation) == Some(self.generation) {
+            scopes.truncate(self.depth);
+        }
+    }
+}
+
+impl<T> fmt::Debug for ScopeGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopeGuard")
+            .field("depth", &self.depth)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_scopes_restore_parent() {
+        let ctx = ContextStack::new();
+        assert_eq!(ctx.current(), None::<&str>);
+
+        let outer = ctx.push_scope("outer");
+        {
+            let _middle = ctx.push_scope("middle");
+            {
+                let _inner = ctx.push_scope("inner");
+                assert_eq!(ctx.current(), Some("inner"));
+                assert_eq!(ctx.depth(), 3);
+            }
+            assert_eq!(ctx.current(), Some("middle"));
+        }
+        assert_eq!(ctx.current(), Some("outer"));
+
+        drop(outer);
+        assert_eq!(ctx.current(), None);
+    }
+
+    #[test]
+    fn test_outer_drop_ends_nested_scopes() {
+        let ctx = ContextStack::new();
+        let outer = ctx.push_scope(1);
+        let inner = ctx.push_scope(2);
+
+        drop(outer);
+        assert_eq!(ctx.depth(), 0);
+        drop(inner);
+        assert_eq!(ctx.current(), None);
+    }
+
+    #[test]
+    fn test_stale_guard_leaves_later_scopes() {
+        let ctx = ContextStack::new();
+        let a = ctx.push_scope('A');
+        let b = ctx.push_scope('B');
+        drop(a);
+
+        let _c = ctx.push_scope('C');
+        let _d = ctx.push_scope('D');
+        // `b` had depth 1, which `d` now occupies.
+        drop(b);
+        assert_eq!(ctx.depth(), 2);
+        assert_eq!(ctx.current(), Some('D'));
+        assert_eq!(format!("{:?}", ctx), "ContextStack(['C', 'D'])");
+    }
+}
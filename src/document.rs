@@ -0,0 +1,154 @@
+//! A read-mostly shared value with copy-on-write updates.
+
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use crate::RwLockExt;
+
+/// A shared document whose readers take cheap `Arc<T>` snapshots.
+///
+/// [`read`](Document::read) only holds the read lock long enough to clone the
+/// inner `Arc`, so readers never block each other for the duration of their
+/// work. [`update`](Document::update) clones the current value, applies the
+/// change to the copy and publishes it as a new `Arc`; snapshots taken before
+/// the update keep seeing the old version. If the update closure panics, the
+/// published document is left unchanged.
+///
+/// Clones share the same document.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::Document;
+///
+///     let config = Document::new(vec!["a"]);
+///     let before = config.read();
+///     config.update(|v| v.push("b"));
+///
+///     assert_eq!(*before, ["a"]);
+///     assert_eq!(*config.read(), ["a", "b"]);
+/// }
+/// ```
+pub struct Document<T: Clone> {
+    current: Arc<RwLock<Arc<T>>>,
+}
+
+impl<T: Clone> Document<T> {
+    /// Creates a new document holding `value`.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Document {
+            current: Arc::new(RwLock::new(Arc::new(value))),
+        }
+    }
+
+    /// Returns a snapshot of the current version.
+    #[inline]
+    pub fn read(&self) -> Arc<T> {
+        Arc::clone(&self.current.read_ignore_poison())
+    }
+
+    /// Applies `f` to a copy of the current version and publishes the result.
+    ///
+    /// Concurrent updates are serialized by the write lock, so none are lost.
+    pub fn update<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let mut current = self.current.write_ignore_poison();
+        let mut next = T::clone(&current);
+        let result = f(&mut next);
+        *current = Arc::new(next);
+        result
+    }
+
+    /// Replaces the document with `value`, returning the previous version.
+    #[inline]
+    pub fn set(&self, value: T) -> Arc<T> {
+        std::mem::replace(&mut *self.current.write_ignore_poison(), Arc::new(value))
+    }
+}
+
+impl<T: Clone> Clone for Document<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Document {
+            current: Arc::clone(&self.current),
+        }
+    }
+}
+
+impl<T: Clone + Default> Default for Document<T> {
+    fn default() -> Self {
+        Document::new(T::default())
+    }
+}
+
+impl<T: Clone + fmt::Debug> fmt::Debug for Document<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Document").field(&*self.read()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::thread;
+
+    #[test]
+    fn test_old_snapshot_unaffected_by_update() {
+        let doc = Document::new(String::from("v1"));
+        let old = doc.read();
+
+        let len = doc.update(|s| {
+            s.push_str("-edited");
+            s.len()
+        });
+        assert_eq!(len, 9);
+        assert_eq!(*old, "v1");
+        assert_eq!(*doc.read(), "v1-edited");
+        assert!(!Arc::ptr_eq(&old, &doc.read()));
+
+        let previous = doc.set(String::from("v2"));
+        assert_eq!(*previous, "v1-edited");
+        assert_eq!(*doc.read(), "v2");
+    }
+
+    #[test]
+    fn test_concurrent_updates_are_not_lost() {
+        let doc = Document::new(0u32);
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let doc = doc.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        let snapshot = doc.read();
+                        assert!(*snapshot <= 400);
+                        doc.update(|n| *n += 1);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*doc.read(), 400);
+    }
+
+    #[test]
+    fn test_panicking_update_leaves_document() {
+        let doc = Document::new(vec![1, 2]);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            doc.update(|v| {
+                v.clear();
+                panic!("update failed");
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(*doc.read(), [1, 2]);
+    }
+}
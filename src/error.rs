@@ -0,0 +1,49 @@
+//! Error wrappers used by [`WrapExt`](crate::WrapExt).
+
+use std::error::Error;
+use std::fmt;
+
+/// Boxed, thread-safe trait object used as the error type throughout the crate.
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+/// An error that displays as its wrapped value and reports a separate cause
+/// through [`Error::source`].
+///
+/// Created by [`WrapExt::into_dyn_error_with_source`](crate::WrapExt::into_dyn_error_with_source).
+#[derive(Debug)]
+pub struct SourcedError<E> {
+    inner: E,
+    source: BoxError,
+}
+
+impl<E> SourcedError<E> {
+    /// Creates a new error wrapping `inner` with `source` as its cause.
+    #[inline(always)]
+    pub fn new(inner: E, source: BoxError) -> Self {
+        SourcedError { inner, source }
+    }
+
+    /// Returns a reference to the wrapped value.
+    #[inline(always)]
+    pub fn get_ref(&self) -> &E {
+        &self.inner
+    }
+
+    /// Consumes the error, returning the wrapped value and its cause.
+    #[inline(always)]
+    pub fn into_parts(self) -> (E, BoxError) {
+        (self.inner, self.source)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for SourcedError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> Error for SourcedError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
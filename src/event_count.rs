@@ -0,0 +1,186 @@
+//! An event count for blocking on arbitrary conditions without spinning.
+
+use std::fmt;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::thread::{self, Thread};
+
+use crate::wide_atomic::AtomicU64;
+use crate::MutexExt;
+
+/// A ticket from [`EventCount::prepare_wait`], consumed by
+/// [`EventCount::wait`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[must_use = "pass the token to `wait` after re-checking the condition"]
+pub struct WaitToken(u64);
+
+/// Lets threads sleep until some condition may have changed, without a lock
+/// around the condition itself.
+///
+/// A waiter calls [`prepare_wait`](EventCount::prepare_wait), re-checks its
+/// condition (for example a lock-free queue being non-empty), and only then
+/// calls [`wait`](EventCount::wait) with the token. A notifier changes the
+/// condition and then calls [`notify_all`](EventCount::notify_all). Because
+/// every notification bumps an epoch in an `AtomicU64`, a notification that
+/// lands between the check and the `wait` is never lost: `wait` returns
+/// immediately if the epoch moved since the token was taken.
+///
+/// Blocked threads sleep with `thread::park` and are woken with `unpark`.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::EventCount;
+///     use std::sync::atomic::{AtomicBool, Ordering};
+///     use std::sync::Arc;
+///     use std::thread;
+///
+///     let events = Arc::new(EventCount::new());
+///     let ready = Arc::new(AtomicBool::new(false));
+///
+///     let (e, r) = (Arc::clone(&events), Arc::clone(&ready));
+///     let notifier = thread::spawn(move || {
+///         r.store(true, Ordering::Release);
+///         e.notify_all();
+///     });
+///
+///     loop {
+///         let token = events.prepare_wait();
+///         if ready.load(Ordering::Acquire) {
+///             break;
+///         }
+///         events.wait(token);
+///     }
+///     notifier.join().unwrap();
+/// }
+/// ```
+#[derive(Default)]
+pub struct EventCount {
+    epoch: AtomicU64,
+    waiters: Mutex<Vec<Thread>>,
+}
+
+impl EventCount {
+    /// Creates an event count with no waiters.
+    #[inline]
+    pub const fn new() -> Self {
+        EventCount {
+            epoch: AtomicU64::new(0),
+            waiters: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Takes a token for the current epoch. Check the wait condition after
+    /// calling this and before [`wait`](EventCount::wait).
+    #[inline]
+    pub fn prepare_wait(&self) -> WaitToken {
+        WaitToken(self.epoch.load(Ordering::SeqCst))
+    }
+
+    /// Blocks until a notification arrives after `token` was taken.
+    ///
+    /// Returns immediately if one already has.
+    pub fn wait(&self, token: WaitToken) {
+        let me = thread::current();
+        self.waiters.lock_ignore_poison().push(me.clone());
+
+        // Registering before re-reading the epoch pairs with `notify_all`
+        // bumping it before draining the waiters: either we see the new
+        // epoch here, or the notifier sees us in the list and unparks us.
+        while self.epoch.load(Ordering::SeqCst) == token.0 {
+            thread::park();
+        }
+
+        self.waiters
+            .lock_ignore_poison()
+            .retain(|waiter| waiter.id() != me.id());
+    }
+
+    /// Wakes every thread blocked in [`wait`](EventCount::wait).
+    pub fn notify_all(&self) {
+        self.epoch.fetch_add(1, Ordering::SeqCst);
+        let waiters = std::mem::take(&mut *self.waiters.lock_ignore_poison());
+        for waiter in waiters {
+            waiter.unpark();
+        }
+    }
+
+    /// Returns the number of threads currently registered as waiting.
+    #[inline]
+    pub fn waiters(&self) -> usize {
+        self.waiters.lock_ignore_poison().len()
+    }
+}
+
+impl fmt::Debug for EventCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventCount")
+            .field("epoch", &self.epoch.load(Ordering::Relaxed))
+            .field("waiters", &self.waiters())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_waiter_woken_by_notifier() {
+        let events = Arc::new(EventCount::new());
+        let value = Arc::new(AtomicUsize::new(0));
+
+        let (e, v) = (Arc::clone(&events), Arc::clone(&value));
+        let waiter = thread::spawn(move || loop {
+            let token = e.prepare_wait();
+            let seen = v.load(Ordering::Acquire);
+            if seen != 0 {
+                return seen;
+            }
+            e.wait(token);
+        });
+
+        while events.waiters() == 0 {
+            thread::yield_now();
+        }
+        value.store(42, Ordering::Release);
+        events.notify_all();
+
+        assert_eq!(waiter.join().unwrap(), 42);
+        assert_eq!(events.waiters(), 0);
+    }
+
+    #[test]
+    fn test_stale_token_returns_immediately() {
+        let events = EventCount::new();
+        let token = events.prepare_wait();
+        events.notify_all();
+        events.wait(token);
+        assert_eq!(events.waiters(), 0);
+    }
+
+    #[test]
+    fn test_notify_wakes_all() {
+        let events = Arc::new(EventCount::new());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let events = Arc::clone(&events);
+                let token = events.prepare_wait();
+                thread::spawn(move || events.wait(token))
+            })
+            .collect();
+
+        while events.waiters() < 4 {
+            thread::sleep(Duration::from_millis(1));
+        }
+        events.notify_all();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! A shared mutex that records how often each thread acquires it.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, LockResult, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, ThreadId};
+
+type Slot<T> = (T, HashMap<ThreadId, u64>);
+
+/// An `Arc<Mutex<T>>` instrumented with per-thread acquisition counts.
+///
+/// Comparing the counts from [`stats`](FairSharedMut::stats) across worker
+/// threads makes lock starvation visible: a thread that rarely wins the lock
+/// shows up with a conspicuously low count.
+///
+/// Cloning is cheap and shares both the value and the statistics.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::FairSharedMut;
+///
+///     let shared = FairSharedMut::new(0u32);
+///     *shared.lock().unwrap() += 1;
+///     let stats = shared.stats();
+///     assert_eq!(stats[&std::thread::current().id()], 1);
+/// }
+/// ```
+pub struct FairSharedMut<T> {
+    inner: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> FairSharedMut<T> {
+    /// Creates a new instrumented shared value.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        FairSharedMut {
+            inner: Arc::new(Mutex::new((value, HashMap::new()))),
+        }
+    }
+
+    /// Acquires the lock, recording the acquisition for the calling thread.
+    ///
+    /// Poisoning is reported the same way as `Mutex::lock`; the acquisition is
+    /// counted either way.
+    pub fn lock(&self) -> LockResult<FairGuard<'_, T>> {
+        let id = thread::current().id();
+        match self.inner.lock() {
+            Ok(mut guard) => {
+                *guard.1.entry(id).or_insert(0) += 1;
+                Ok(FairGuard { guard })
+            }
+            Err(poisoned) => {
+                let mut guard = poisoned.into_inner();
+                *guard.1.entry(id).or_insert(0) += 1;
+                Err(PoisonError::new(FairGuard { guard }))
+            }
+        }
+    }
+
+    /// Returns a snapshot of the acquisition count for every thread that has
+    /// locked the value.
+    pub fn stats(&self) -> HashMap<ThreadId, u64> {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .1
+            .clone()
+    }
+
+    /// Clears all recorded acquisition counts.
+    pub fn reset_stats(&self) {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .1
+            .clear();
+    }
+}
+
+impl<T> Clone for FairSharedMut<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        FairSharedMut {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: Default> Default for FairSharedMut<T> {
+    fn default() -> Self {
+        FairSharedMut::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for FairSharedMut<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inner.try_lock() {
+            Ok(guard) => f
+                .debug_struct("FairSharedMut")
+                .field("value", &guard.0)
+                .field("stats", &guard.1)
+                .finish(),
+            Err(_) => f
+                .debug_struct("FairSharedMut")
+                .field("value", &format_args!("<locked>"))
+                .finish(),
+        }
+    }
+}
+
+/// Guard returned by [`FairSharedMut::lock`], dereferencing to the value.
+pub struct FairGuard<'a, T> {
+    guard: MutexGuard<'a, Slot<T>>,
+}
+
+impl<T> Deref for FairGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.guard.0
+    }
+}
+
+impl<T> DerefMut for FairGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard.0
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for FairGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.guard.0, f)
+    }
+}
+
+/// Serializes the protected value only; acquisition statistics are not
+/// persisted and serializing does not count as an acquisition.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for FairSharedMut<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .0
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for FairSharedMut<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(FairSharedMut::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_per_thread() {
+        let shared = FairSharedMut::new(0u64);
+
+        let handles: Vec<_> = (1..=3u64)
+            .map(|n| {
+                let shared = shared.clone();
+                thread::spawn(move || {
+                    for _ in 0..n * 10 {
+                        *shared.lock().unwrap() += 1;
+                    }
+                    thread::current().id()
+                })
+            })
+            .collect();
+        let ids: Vec<ThreadId> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        let stats = shared.stats();
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[&ids[0]], 10);
+        assert_eq!(stats[&ids[1]], 20);
+        assert_eq!(stats[&ids[2]], 30);
+        assert_eq!(*shared.lock().unwrap(), 60);
+    }
+
+    #[test]
+    fn test_reset_stats() {
+        let shared = FairSharedMut::new(String::new());
+        shared.lock().unwrap().push('a');
+        assert_eq!(shared.stats().values().sum::<u64>(), 1);
+
+        shared.reset_stats();
+        assert!(shared.stats().is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let shared = FairSharedMut::new(String::from("config"));
+        let json = serde_json::to_string(&shared).unwrap();
+        assert_eq!(json, "\"config\"");
+        assert!(shared.stats().is_empty());
+
+        let back: FairSharedMut<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(*back.lock().unwrap(), "config");
+    }
+}
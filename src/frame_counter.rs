@@ -0,0 +1,165 @@
+//! A `Cell`-backed frame counter with a periodic rollover callback.
+
+use std::cell::Cell;
+use std::fmt;
+
+type Callback = Box<dyn FnMut(u64)>;
+
+/// Counts frames and calls a registered callback every `period` ticks.
+///
+/// The count itself never resets: after the callback fires on frame
+/// `period`, counting simply continues to `period + 1`, and the callback
+/// fires again on `2 * period`. The callback receives how many boundaries
+/// have been crossed so far. Everything lives in `Cell`s, so the counter can
+/// be ticked through `&self` from anywhere in a game loop.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::FrameCounter;
+///     use std::rc::Rc;
+///     use std::cell::Cell;
+///
+///     let seconds = Rc::new(Cell::new(0));
+///     let frames = FrameCounter::new(60);
+///     let sink = Rc::clone(&seconds);
+///     frames.on_rollover(move |n| sink.set(n));
+///
+///     for _ in 0..150 {
+///         frames.tick();
+///     }
+///     assert_eq!(frames.frame(), 150);
+///     assert_eq!(seconds.get(), 2);
+/// }
+/// ```
+pub struct FrameCounter {
+    frame: Cell<u64>,
+    period: u64,
+    on_rollover: Cell<Option<Callback>>,
+}
+
+impl FrameCounter {
+    /// Creates a counter at frame `0` with a rollover every `period` frames.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    pub fn new(period: u64) -> Self {
+        assert!(period > 0, "FrameCounter period must be non-zero");
+        FrameCounter {
+            frame: Cell::new(0),
+            period,
+            on_rollover: Cell::new(None),
+        }
+    }
+
+    /// Registers `f` to run on every rollover, replacing any previous callback.
+    pub fn on_rollover<F>(&self, f: F)
+    where
+        F: FnMut(u64) + 'static,
+    {
+        self.on_rollover.set(Some(Box::new(f)));
+    }
+
+    /// Advances the counter by one frame, running the callback if a boundary
+    /// was reached, and returns the new frame number.
+    ///
+    /// The frame number wraps at `u64::MAX`.
+    pub fn tick(&self) -> u64 {
+        let frame = self.frame.get().wrapping_add(1);
+        self.frame.set(frame);
+        // `u64::is_multiple_of` needs Rust 1.87.
+        #[allow(clippy::manual_is_multiple_of)]
+        if frame % self.period == 0 {
+            // Take the callback out while it runs so it may tick or
+            // re-register without aliasing itself.
+            if let Some(mut f) = self.on_rollover.take() {
+                f(frame / self.period);
+                let replaced = self.on_rollover.take();
+                self.on_rollover.set(replaced.or(Some(f)));
+            }
+        }
+        frame
+    }
+
+    /// Returns the current frame number.
+    #[inline(always)]
+    pub fn frame(&self) -> u64 {
+        self.frame.get()
+    }
+
+    /// Returns the configured rollover period.
+    #[inline(always)]
+    pub fn period(&self) -> u64 {
+        self.period
+    }
+
+    /// Returns how many rollover boundaries have been crossed.
+    #[inline(always)]
+    pub fn rollovers(&self) -> u64 {
+        self.frame.get() / self.period
+    }
+}
+
+impl fmt::Debug for FrameCounter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameCounter")
+            .field("frame", &self.frame.get())
+            .field("period", &self.period)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_fires_at_boundary_and_continues() {
+        let fired = Rc::new(Cell::new(Vec::new()));
+        let counter = FrameCounter::new(3);
+        let sink = Rc::clone(&fired);
+        counter.on_rollover(move |n| {
+            let mut seen = sink.take();
+            seen.push(n);
+            sink.set(seen);
+        });
+
+        let frames: Vec<u64> = (0..7).map(|_| counter.tick()).collect();
+        assert_eq!(frames, [1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(fired.take(), [1, 2]);
+        assert_eq!(counter.rollovers(), 2);
+    }
+
+    #[test]
+    fn test_without_callback() {
+        let counter = FrameCounter::new(1);
+        counter.tick();
+        counter.tick();
+        assert_eq!(counter.frame(), 2);
+        assert_eq!(counter.rollovers(), 2);
+    }
+
+    #[test]
+    fn test_callback_can_replace_itself() {
+        let counter = Rc::new(FrameCounter::new(2));
+        let hits = Rc::new(Cell::new(0));
+
+        let (weak, first_hits) = (Rc::downgrade(&counter), Rc::clone(&hits));
+        counter.on_rollover(move |_| {
+            first_hits.set(first_hits.get() + 1);
+            let second_hits = Rc::clone(&first_hits);
+            weak.upgrade()
+                .unwrap()
+                .on_rollover(move |_| second_hits.set(second_hits.get() + 100));
+        });
+
+        for _ in 0..4 {
+            counter.tick();
+        }
+        assert_eq!(hits.get(), 101);
+    }
+}
@@ -0,0 +1,208 @@
+//! A lock-free slot allocator backed by an atomic bitmap.
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use crate::primitives::AtomicU64;
+
+const BITS: usize = u64::BITS as usize;
+
+/// A concurrent allocator handing out slot indices in `0..capacity`.
+///
+/// Each bit of the underlying `Arc<[AtomicU64]>` marks one slot, set while the
+/// slot is free. [`allocate`](FreeList::allocate) claims a bit with a
+/// compare-and-swap, so any number of threads may allocate and free
+/// concurrently without a lock. Cloning shares the same bitmap.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(all(feature = "sync", not(feature = "loom")))]
+/// {
+///     use autowrap::FreeList;
+///
+///     let slots = FreeList::new(2);
+///     let a = slots.allocate().unwrap();
+///     let b = slots.allocate().unwrap();
+///     assert_ne!(a, b);
+///     assert!(slots.allocate().is_none());
+///
+///     slots.free(a);
+///     assert_eq!(slots.allocate(), Some(a));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FreeList {
+    words: Arc<[AtomicU64]>,
+    capacity: usize,
+}
+
+impl FreeList {
+    /// Creates an allocator with `capacity` free slots.
+    pub fn new(capacity: usize) -> Self {
+        let words = (0..capacity.div_ceil(BITS))
+            .map(|i| {
+                let remaining = capacity - i * BITS;
+                let mask = if remaining >= BITS {
+                    u64::MAX
+                } else {
+                    (1u64 << remaining) - 1
+                };
+                AtomicU64::new(mask)
+            })
+            .collect();
+        FreeList { words, capacity }
+    }
+
+    /// Claims a free slot, returning its index, or `None` if all are in use.
+    pub fn allocate(&self) -> Option<usize> {
+        for (i, word) in self.words.iter().enumerate() {
+            let mut current = word.load(Ordering::Relaxed);
+            while current != 0 {
+                let bit = current.trailing_zeros() as usize;
+                let claimed = current & !(1u64 << bit);
+                match word.compare_exchange_weak(current, claimed, Ordering::Acquire, Ordering::Relaxed) {
+                    Ok(_) => return Some(i * BITS + bit),
+                    Err(actual) => current = actual,
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns a slot to the free list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range or the slot is already free.
+    pub fn free(&self, index: usize) {
+        assert!(index < self.capacity, "slot {} out of range", index);
+        let mask = 1u64 << (index % BITS);
+        let previous = self.words[index / BITS].fetch_or(mask, Ordering::Release);
+        assert!(previous & mask == 0, "slot {} freed twice", index);
+    }
+
+    /// Returns `true` if `index` is currently allocated.
+    pub fn is_allocated(&self, index: usize) -> bool {
+        index < self.capacity
+            && self.words[index / BITS].load(Ordering::Relaxed) & (1u64 << (index % BITS)) == 0
+    }
+
+    /// Returns the total number of slots.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of free slots at the moment of the call.
+    pub fn available(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.load(Ordering::Relaxed).count_ones() as usize)
+            .sum()
+    }
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::thread;
+
+    #[test]
+    fn test_allocate_and_free() {
+        let slots = FreeList::new(70);
+        assert_eq!(slots.capacity(), 70);
+        assert_eq!(slots.available(), 70);
+
+        let all: Vec<usize> = (0..70).map(|_| slots.allocate().unwrap()).collect();
+        assert_eq!(all, (0..70).collect::<Vec<_>>());
+        assert!(slots.allocate().is_none());
+        assert!(slots.is_allocated(65));
+
+        slots.free(65);
+        assert!(!slots.is_allocated(65));
+        assert_eq!(slots.allocate(), Some(65));
+    }
+
+    #[test]
+    fn test_empty() {
+        let slots = FreeList::new(0);
+        assert!(slots.allocate().is_none());
+        assert_eq!(slots.available(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "freed twice")]
+    fn test_double_free_panics() {
+        let slots = FreeList::new(4);
+        let slot = slots.allocate().unwrap();
+        slots.free(slot);
+        slots.free(slot);
+    }
+
+    #[test]
+    fn test_concurrent_allocation_is_unique() {
+        const CAPACITY: usize = 1000;
+
+        let slots = FreeList::new(CAPACITY);
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let slots = slots.clone();
+                thread::spawn(move || {
+                    let mut mine = Vec::new();
+                    while let Some(slot) = slots.allocate() {
+                        mine.push(slot);
+                    }
+                    mine
+                })
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+        for handle in handles {
+            for slot in handle.join().unwrap() {
+                assert!(seen.insert(slot), "slot {} allocated twice", slot);
+            }
+        }
+        assert_eq!(seen.len(), CAPACITY);
+        assert_eq!(slots.available(), 0);
+    }
+}
+
+#[cfg(all(test, feature = "loom"))]
+mod loom_tests {
+    use super::*;
+    use loom::thread;
+
+    #[test]
+    fn test_loom_racing_allocations_are_unique() {
+        loom::model(|| {
+            let slots = FreeList::new(2);
+            let other = slots.clone();
+            let handle = thread::spawn(move || other.allocate());
+            let mine = slots.allocate();
+            let theirs = handle.join().unwrap();
+
+            assert!(mine.is_some() && theirs.is_some());
+            assert_ne!(mine, theirs);
+            assert_eq!(slots.available(), 0);
+        });
+    }
+
+    #[test]
+    fn test_loom_free_is_not_lost() {
+        loom::model(|| {
+            let slots = FreeList::new(2);
+            let a = slots.allocate().unwrap();
+            let b = slots.allocate().unwrap();
+
+            let other = slots.clone();
+            let handle = thread::spawn(move || other.free(a));
+            slots.free(b);
+            handle.join().unwrap();
+
+            assert_eq!(slots.available(), 2);
+        });
+    }
+}
@@ -0,0 +1,201 @@
+//! A thread-safe interner that hands out shared `Arc`s for live values.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, Weak};
+
+use crate::MutexExt;
+
+/// Deduplicates values by key while they are alive.
+///
+/// The interner only keeps a `Weak<V>` per key, so it never keeps a value
+/// alive on its own. [`intern`](Interner::intern) returns the existing `Arc`
+/// while any clone of it is still around; once the last one is dropped, the
+/// next `intern` for that key builds a fresh value. Dead entries are swept
+/// out as the map grows, so churning keys don't accumulate.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::Interner;
+///     use std::sync::Arc;
+///
+///     let fonts = Interner::new();
+///     let a = fonts.intern("mono", || String::from("loaded mono"));
+///     let b = fonts.intern("mono", || unreachable!());
+///     assert!(Arc::ptr_eq(&a, &b));
+///
+///     drop((a, b));
+///     let c = fonts.intern("mono", || String::from("reloaded mono"));
+///     assert_eq!(*c, "reloaded mono");
+/// }
+/// ```
+pub struct Interner<K, V> {
+    inner: Mutex<State<K, V>>,
+}
+
+struct State<K, V> {
+    entries: HashMap<K, Weak<V>>,
+    next_sweep: usize,
+}
+
+const MIN_SWEEP: usize = 16;
+
+impl<K, V> Interner<K, V> {
+    /// Creates an empty interner.
+    #[inline]
+    pub fn new() -> Self {
+        Interner {
+            inner: Mutex::new(State {
+                entries: HashMap::new(),
+                next_sweep: MIN_SWEEP,
+            }),
+        }
+    }
+
+    /// Returns the number of keys whose value is still alive.
+    pub fn len(&self) -> usize {
+        let state = self.inner.lock_ignore_poison();
+        state
+            .entries
+            .values()
+            .filter(|weak| weak.strong_count() > 0)
+            .count()
+    }
+
+    /// Returns `true` if no interned value is alive.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K: Eq + Hash, V> Interner<K, V> {
+    /// Returns the live value for `key`, or builds one with `f` and interns
+    /// it.
+    ///
+    /// `f` runs while the interner's lock is held, so two threads interning
+    /// the same key never build it twice. It must not call back into the same
+    /// interner.
+    pub fn intern<F>(&self, key: K, f: F) -> Arc<V>
+    where
+        F: FnOnce() -> V,
+    {
+        let mut state = self.inner.lock_ignore_poison();
+        if let Some(live) = state.entries.get(&key).and_then(Weak::upgrade) {
+            return live;
+        }
+
+        let value = Arc::new(f());
+        state.entries.insert(key, Arc::downgrade(&value));
+        if state.entries.len() >= state.next_sweep {
+            state.sweep();
+        }
+        value
+    }
+
+    /// Returns the live value for `key` without building one.
+    pub fn get(&self, key: &K) -> Option<Arc<V>> {
+        self.inner
+            .lock_ignore_poison()
+            .entries
+            .get(key)
+            .and_then(Weak::upgrade)
+    }
+
+    /// Removes every entry whose value has been dropped.
+    pub fn purge(&self) {
+        self.inner.lock_ignore_poison().sweep();
+    }
+}
+
+impl<K: Eq + Hash, V> State<K, V> {
+    fn sweep(&mut self) {
+        self.entries.retain(|_, weak| weak.strong_count() > 0);
+        // Doubling keeps the sweeping cost amortized over the inserts.
+        self.next_sweep = (self.entries.len() * 2).max(MIN_SWEEP);
+    }
+}
+
+impl<K, V> Default for Interner<K, V> {
+    #[inline]
+    fn default() -> Self {
+        Interner::new()
+    }
+}
+
+impl<K, V> fmt::Debug for Interner<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interner").field("live", &self.len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_same_key_shares_arc() {
+        let interner = Interner::new();
+        let a = interner.intern(1u32, || vec![1u8, 2]);
+        let b = interner.intern(1, || unreachable!());
+
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(Arc::strong_count(&a), 2);
+        assert_eq!(Arc::weak_count(&a), 1);
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn test_dropped_value_is_rebuilt() {
+        let interner = Interner::new();
+        let first = interner.intern("k", || String::from("first"));
+        drop(first);
+        assert!(interner.get(&"k").is_none());
+        assert!(interner.is_empty());
+
+        let second = interner.intern("k", || String::from("second"));
+        assert_eq!(*second, "second");
+        assert_eq!(Arc::strong_count(&second), 1);
+    }
+
+    #[test]
+    fn test_dead_entries_are_swept() {
+        let interner = Interner::new();
+        for key in 0..1000u32 {
+            drop(interner.intern(key, || key));
+        }
+        assert!(interner.inner.lock_ignore_poison().entries.len() < MIN_SWEEP);
+
+        let kept = interner.intern(5000, || 0);
+        interner.purge();
+        assert_eq!(interner.inner.lock_ignore_poison().entries.len(), 1);
+        drop(kept);
+    }
+
+    #[test]
+    fn test_concurrent_interns_build_once() {
+        let interner = Interner::new();
+        let builds = std::sync::atomic::AtomicUsize::new(0);
+        let values: Vec<_> = thread::scope(|s| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    s.spawn(|| {
+                        interner.intern("shared", || {
+                            builds.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                            42u64
+                        })
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        assert_eq!(builds.into_inner(), 1);
+        assert!(values.iter().all(|v| Arc::ptr_eq(v, &values[0])));
+    }
+}
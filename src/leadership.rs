@@ -0,0 +1,140 @@
+//! An in-process leader election token.
+
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+
+use crate::MutexExt;
+
+/// A token that at most one thread may hold at a time.
+///
+/// [`try_acquire`](Leadership::try_acquire) never blocks: it succeeds when the
+/// token is free or already held by the calling thread. Clones share the same
+/// token.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::Leadership;
+///
+///     let token = Leadership::new();
+///     assert!(token.try_acquire());
+///     assert!(token.is_leader());
+///
+///     let other = token.clone();
+///     let won = std::thread::spawn(move || other.try_acquire()).join().unwrap();
+///     assert!(!won);
+///
+///     assert!(token.release());
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Leadership {
+    owner: Arc<Mutex<Option<ThreadId>>>,
+}
+
+impl Leadership {
+    /// Creates an unowned token.
+    #[inline]
+    pub fn new() -> Self {
+        Leadership::default()
+    }
+
+    /// Takes the token if it is free, returning `true` if the calling thread
+    /// is the leader afterwards.
+    pub fn try_acquire(&self) -> bool {
+        let me = thread::current().id();
+        let mut owner = self.owner.lock_ignore_poison();
+        match *owner {
+            Some(id) => id == me,
+            None => {
+                *owner = Some(me);
+                true
+            }
+        }
+    }
+
+    /// Gives up the token if the calling thread holds it.
+    ///
+    /// Returns `false` if another thread (or nobody) holds it.
+    pub fn release(&self) -> bool {
+        let me = thread::current().id();
+        let mut owner = self.owner.lock_ignore_poison();
+        if *owner == Some(me) {
+            *owner = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns `true` if the calling thread holds the token.
+    pub fn is_leader(&self) -> bool {
+        *self.owner.lock_ignore_poison() == Some(thread::current().id())
+    }
+
+    /// Returns the thread currently holding the token.
+    pub fn leader(&self) -> Option<ThreadId> {
+        *self.owner.lock_ignore_poison()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn test_acquire_is_reentrant() {
+        let token = Leadership::new();
+        assert_eq!(token.leader(), None);
+        assert!(token.try_acquire());
+        assert!(token.try_acquire());
+        assert_eq!(token.leader(), Some(thread::current().id()));
+        assert!(token.release());
+        assert!(!token.release());
+    }
+
+    #[test]
+    fn test_only_owner_can_release() {
+        let token = Leadership::new();
+        assert!(token.try_acquire());
+
+        let other = token.clone();
+        assert!(!thread::spawn(move || other.release()).join().unwrap());
+        assert!(token.is_leader());
+    }
+
+    #[test]
+    fn test_single_leader_at_a_time() {
+        let token = Leadership::new();
+        let active = Arc::new(AtomicUsize::new(0));
+        let terms = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let (token, active, terms) = (token.clone(), Arc::clone(&active), Arc::clone(&terms));
+                thread::spawn(move || {
+                    for _ in 0..20 {
+                        if token.try_acquire() {
+                            assert_eq!(active.fetch_add(1, Ordering::SeqCst), 0);
+                            terms.fetch_add(1, Ordering::SeqCst);
+                            thread::sleep(Duration::from_micros(200));
+                            active.fetch_sub(1, Ordering::SeqCst);
+                            assert!(token.release());
+                        }
+                        thread::yield_now();
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(terms.load(Ordering::SeqCst) > 0);
+        assert_eq!(token.leader(), None);
+    }
+}
@@ -11,24 +11,290 @@
 //! - Feature-gated thread-safe types
 
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 #![doc(test(attr(allow(unused_literals))))]
 
 #[cfg(feature = "std")]
 extern crate std as core;
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+extern crate alloc;
+
 #[cfg(feature = "std")]
-use core::cell::RefCell;
+use core::cell::{BorrowError, BorrowMutError, RefCell};
 
-#[cfg(all(feature = "std", feature = "sync"))]
-use core::sync::atomic::{AtomicUsize, AtomicIsize, AtomicU32, AtomicI32, AtomicU64, AtomicI64, AtomicBool};
+#[cfg(feature = "sync")]
+use core::sync::atomic::{AtomicUsize, AtomicIsize, AtomicU32, AtomicI32, AtomicBool, AtomicPtr, Ordering};
+
+#[cfg(all(feature = "sync", any(target_has_atomic = "64", feature = "portable-atomic")))]
+use wide_atomic::{AtomicU64, AtomicI64};
+
+#[cfg(feature = "portable-atomic")]
+use wide_atomic::{AtomicU128, AtomicI128};
 
 #[cfg(feature = "std")]
 use std::rc::Rc;
 
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+use core::cell::{Cell, OnceCell, UnsafeCell};
+use core::cmp::Reverse;
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+#[cfg(all(feature = "std", feature = "sync"))]
+use core::pin::Pin;
+
+#[cfg(all(feature = "std", feature = "sync", any(target_has_atomic = "64", feature = "portable-atomic")))]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "derive")]
+pub use autowrap_derive::WrapFields;
+
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub mod __private {
+    pub use core::pin::Pin;
+    pub use std::boxed::Box;
+    pub use std::rc::Rc;
+
+    #[cfg(feature = "sync")]
+    pub use std::sync::{Arc, OnceLock};
+}
+
+mod priority_flag;
+
+pub use priority_flag::PriorityFlag;
+
+mod by_address;
+
+pub use by_address::ByAddress;
+
+mod retry_state;
+
+pub use retry_state::RetryState;
+
+#[cfg(feature = "std")]
+mod error;
+
+#[cfg(feature = "std")]
+pub use error::{BoxError, SourcedError};
+
+#[cfg(feature = "std")]
+mod builder;
+
+#[cfg(feature = "std")]
+pub use builder::Builder;
+
+#[cfg(feature = "std")]
+mod computed;
+
+#[cfg(feature = "std")]
+pub use computed::Computed;
+
+#[cfg(feature = "std")]
+mod container;
+
+#[cfg(feature = "std")]
+pub use container::Container;
+
+#[cfg(feature = "std")]
+mod context_stack;
+
+#[cfg(feature = "std")]
+pub use context_stack::{ContextStack, ScopeGuard};
+
+#[cfg(feature = "std")]
+mod frame_counter;
+
+#[cfg(feature = "std")]
+pub use frame_counter::FrameCounter;
+
+#[cfg(feature = "std")]
+mod rc_cell;
+
+#[cfg(feature = "std")]
+pub use rc_cell::RcCell;
+
+#[cfg(feature = "std")]
+mod symbol_table;
+
+#[cfg(feature = "std")]
+pub use symbol_table::SymbolTable;
+
+#[cfg(feature = "std")]
+mod tracked_refcell;
+
+#[cfg(feature = "std")]
+pub use tracked_refcell::{TrackedRef, TrackedRefCell, TrackedRefMut};
+
+#[cfg(feature = "std")]
+mod thunk;
+
+#[cfg(feature = "std")]
+pub use thunk::Thunk;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod arc_cow;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use arc_cow::ArcCow;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod atomic_cell;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use atomic_cell::{AtomicCell, NoPadding};
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod atomic_enum;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use atomic_enum::{AtomicEnum, AtomicEnumRepr};
+
+#[cfg(all(feature = "std", feature = "sync", any(target_has_atomic = "64", feature = "portable-atomic")))]
+mod atomic_time;
+
+#[cfg(all(feature = "std", feature = "sync", any(target_has_atomic = "64", feature = "portable-atomic")))]
+pub use atomic_time::{AtomicDuration, AtomicInstant};
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod cached_weak;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use cached_weak::CachedWeak;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod concurrent_queue;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use concurrent_queue::ConcurrentQueue;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod document;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use document::Document;
+
+#[cfg(all(feature = "std", feature = "sync", any(target_has_atomic = "64", feature = "portable-atomic")))]
+mod event_count;
+
+#[cfg(all(feature = "std", feature = "sync", any(target_has_atomic = "64", feature = "portable-atomic")))]
+pub use event_count::{EventCount, WaitToken};
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod fair_shared_mut;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use fair_shared_mut::{FairGuard, FairSharedMut};
+
+#[cfg(all(feature = "std", feature = "sync", any(target_has_atomic = "64", feature = "portable-atomic")))]
+mod free_list;
+
+#[cfg(all(feature = "std", feature = "sync", any(target_has_atomic = "64", feature = "portable-atomic")))]
+pub use free_list::FreeList;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod interner;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod primitives;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use interner::Interner;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod leadership;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use leadership::Leadership;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod monitor;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use monitor::Monitor;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod persistent_stack;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use persistent_stack::{PersistentStack, PersistentStackIter};
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod prefer_writer_rwlock;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use prefer_writer_rwlock::{PreferWriterReadGuard, PreferWriterRwLock, PreferWriterWriteGuard};
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod semaphore;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use semaphore::Semaphore;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod small_arc;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use small_arc::SmallArc;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod spsc_ring;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use spsc_ring::{SpscConsumer, SpscProducer, SpscRing};
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod swap_slot;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use swap_slot::SwapSlot;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod transactional;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+pub use transactional::Transactional;
+
+#[cfg(all(feature = "std", feature = "sync"))]
+mod tracked_mutex;
+
 #[cfg(all(feature = "std", feature = "sync"))]
-use std::sync::{Arc, Mutex, RwLock, OnceLock};
+pub use tracked_mutex::TrackedMutex;
+
+#[cfg(all(feature = "deadlock_debug", debug_assertions))]
+pub use tracked_mutex::TrackedGuard;
+
+#[cfg(feature = "parking_lot")]
+mod shared_mut;
+
+#[cfg(feature = "parking_lot")]
+pub use shared_mut::{LockTimeout, SharedMut, SharedMutBuilder};
 
-use core::cell::Cell;
+#[cfg(feature = "sync")]
+mod spin_lock;
+
+#[cfg(feature = "sync")]
+pub use spin_lock::{SpinGuard, SpinLock};
+
+#[cfg(all(feature = "sync", target_has_atomic = "64"))]
+mod tagged_ptr;
+
+#[cfg(all(feature = "sync", target_has_atomic = "64"))]
+pub use tagged_ptr::TaggedPtr;
+
+#[cfg(feature = "sync")]
+mod ticket_lock;
+
+#[cfg(feature = "sync")]
+pub use ticket_lock::{TicketGuard, TicketLock};
+
+#[cfg(feature = "sync")]
+mod wide_atomic;
 
 // ============================================================================
 // WrapExt Trait - Smart Pointer Wrappers
@@ -63,7 +329,8 @@ use core::cell::Cell;
 pub trait WrapExt: Sized {
     /// Wraps the value in a `Cell<T>`.
     ///
-    /// Requires `T: Copy` for interior mutability.
+    /// Requires `T: Copy` for interior mutability; see
+    /// [`cell_move`](WrapExt::cell_move) for move-only contents.
     #[inline(always)]
     fn cell(self) -> Cell<Self>
     where
@@ -72,6 +339,129 @@ pub trait WrapExt: Sized {
         Cell::new(self)
     }
 
+    /// Wraps the value in a `Cell<T>` without requiring `T: Copy`.
+    ///
+    /// Constructing a `Cell` is always sound; only `get` needs `Copy`. For
+    /// other contents the value moves in and out through `set`, `replace`,
+    /// `take` (for `T: Default`) and `into_inner`.
+    #[inline(always)]
+    fn cell_move(self) -> Cell<Self> {
+        Cell::new(self)
+    }
+
+    /// Wraps the value in a `Cell<Option<T>>` holding `Some(self)`.
+    ///
+    /// Unlike [`cell`](WrapExt::cell) this works for any `T`: the value is
+    /// moved out and back in through [`CellOptionExt`] instead of copied.
+    #[inline(always)]
+    fn cell_option(self) -> Cell<Option<Self>> {
+        Cell::new(Some(self))
+    }
+
+    /// Wraps the value in an `UnsafeCell<T>`.
+    ///
+    /// `UnsafeCell` is the only sound way to mutate through a shared
+    /// reference, and the building block of every other cell type. The
+    /// pointer returned by `get` may be written through, but the caller must
+    /// uphold the aliasing rules: no `&mut T` may coexist with any other
+    /// reference to the value, and no `&T` may be alive while it is written.
+    #[inline(always)]
+    fn unsafe_cell(self) -> UnsafeCell<Self> {
+        UnsafeCell::new(self)
+    }
+
+    /// Wraps the value in an already-initialized `OnceCell<T>`.
+    ///
+    /// Use [`once_cell_empty`] for a cell that starts out empty.
+    #[inline(always)]
+    fn once_cell(self) -> OnceCell<Self> {
+        OnceCell::from(self)
+    }
+
+    /// Splits a pair into two independent `Cell`s.
+    ///
+    /// Clearer than `Cell<(A, B)>` when the two halves change separately.
+    /// Two-element arrays are accepted as well.
+    #[inline(always)]
+    fn cell_pair<A, B>(self) -> (Cell<A>, Cell<B>)
+    where
+        Self: Into<(A, B)>,
+        A: Copy,
+        B: Copy,
+    {
+        let (a, b) = self.into();
+        (Cell::new(a), Cell::new(b))
+    }
+
+    /// Wraps the value in a `ManuallyDrop<T>`.
+    ///
+    /// The value's destructor will not run unless it is recovered with
+    /// `ManuallyDrop::into_inner` or dropped explicitly.
+    #[inline(always)]
+    fn manually_drop(self) -> ManuallyDrop<Self> {
+        ManuallyDrop::new(self)
+    }
+
+    /// Wraps the value in a `MaybeUninit<T>`.
+    ///
+    /// The result is typed as possibly-uninitialized but is in fact fully
+    /// initialized with `self`, so calling `assume_init` on it is always safe.
+    #[inline(always)]
+    fn maybe_uninit(self) -> MaybeUninit<Self> {
+        MaybeUninit::new(self)
+    }
+
+    /// Wraps the value in `core::cmp::Reverse` to flip its ordering.
+    ///
+    /// Handy for turning the max-heap `BinaryHeap` into a min-heap:
+    ///
+    /// ```
+    /// #[cfg(feature = "std")]
+    /// {
+    ///     use std::collections::BinaryHeap;
+    ///     use autowrap::WrapExt;
+    ///
+    ///     let mut heap = BinaryHeap::new();
+    ///     for n in [5, 1, 8, 3] {
+    ///         heap.push(n.reversed());
+    ///     }
+    ///
+    ///     let ascending: Vec<i32> = std::iter::from_fn(|| heap.pop().map(|r| r.0)).collect();
+    ///     assert_eq!(ascending, [1, 3, 5, 8]);
+    /// }
+    /// ```
+    #[inline(always)]
+    fn reversed(self) -> Reverse<Self> {
+        Reverse(self)
+    }
+
+    /// Wraps the value in `Some`.
+    #[inline(always)]
+    fn some(self) -> Option<Self> {
+        Some(self)
+    }
+
+    /// Wraps the value in `Ok`.
+    ///
+    /// The error type usually has to be named with a turbofish, e.g.
+    /// `5.ok::<String>()`, unless inference can pick it up from context. On a
+    /// value that is already a `Result`, the inherent `Result::ok` takes
+    /// precedence; call `WrapExt::ok(value)` to wrap it instead.
+    #[inline(always)]
+    fn ok<E>(self) -> Result<Self, E> {
+        Ok(self)
+    }
+
+    /// Wraps the value in `Err`.
+    ///
+    /// As with [`ok`](WrapExt::ok), name the success type with a turbofish,
+    /// e.g. `"bad input".err::<i32>()`, and use `WrapExt::err(value)` on a
+    /// value that is already a `Result`.
+    #[inline(always)]
+    fn err<T>(self) -> Result<T, Self> {
+        Err(self)
+    }
+
     /// Wraps the value in a `RefCell<T>`.
     #[cfg(feature = "std")]
     #[inline(always)]
@@ -79,6 +469,30 @@ pub trait WrapExt: Sized {
         RefCell::new(self)
     }
 
+    /// Creates a `Box<[T]>` holding `n` clones of the value.
+    ///
+    /// The slice is allocated once at its exact length. With `n == 0` the
+    /// value is dropped and the slice is empty.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn boxed_slice_repeat(self, n: usize) -> Box<[Self]>
+    where
+        Self: Clone,
+    {
+        vec![self; n].into_boxed_slice()
+    }
+
+    /// Moves the value into a `Box<T>`, returning `AllocError` instead of
+    /// aborting if the allocation fails.
+    ///
+    /// Requires the `allocator_api` feature, which needs a nightly compiler
+    /// until `Box::try_new` is stabilized.
+    #[cfg(feature = "allocator_api")]
+    #[inline(always)]
+    fn try_boxed(self) -> Result<Box<Self>, core::alloc::AllocError> {
+        Box::try_new(self)
+    }
+
     /// Wraps the value in an `Rc<T>`.
     #[cfg(feature = "std")]
     #[inline(always)]
@@ -86,6 +500,25 @@ pub trait WrapExt: Sized {
         Rc::new(self)
     }
 
+    /// Wraps the value in an `Rc<T>`, returning `AllocError` instead of
+    /// aborting if the allocation fails.
+    ///
+    /// Requires the `allocator_api` feature (nightly); see
+    /// [`try_boxed`](WrapExt::try_boxed).
+    #[cfg(feature = "allocator_api")]
+    #[inline(always)]
+    fn try_rc(self) -> Result<Rc<Self>, core::alloc::AllocError> {
+        Rc::try_new(self)
+    }
+
+    /// Wraps the value in an `Rc<T>` and returns two handles to it.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn rc_pair(self) -> (Rc<Self>, Rc<Self>) {
+        let rc = Rc::new(self);
+        (Rc::clone(&rc), rc)
+    }
+
     /// Wraps the value in an `Rc<RefCell<T>>`.
     #[cfg(feature = "std")]
     #[inline(always)]
@@ -93,6 +526,24 @@ pub trait WrapExt: Sized {
         Rc::new(RefCell::new(self))
     }
 
+    /// Wraps the value in an [`RcCell<T>`], a cloneable `Rc<RefCell<T>>`
+    /// handle with borrow methods of its own.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn rc_cell_shared(self) -> RcCell<Self> {
+        RcCell::new(self)
+    }
+
+    /// Wraps the value in a `TrackedRefCell<T>`.
+    ///
+    /// With the `debug_refcell` feature in a debug build, the cell records
+    /// where it is borrowed; otherwise it behaves as a plain `RefCell`.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn tracked_refcell(self) -> TrackedRefCell<Self> {
+        TrackedRefCell::new(self)
+    }
+
     /// Wraps the value in an `Arc<T>`.
     #[cfg(all(feature = "std", feature = "sync"))]
     #[inline(always)]
@@ -100,36 +551,315 @@ pub trait WrapExt: Sized {
         Arc::new(self)
     }
 
-    /// Wraps the value in an `Arc<Mutex<T>>`.
-    #[cfg(all(feature = "std", feature = "sync"))]
+    /// Wraps the value in an `Arc<T>`, returning `AllocError` instead of
+    /// aborting if the allocation fails.
+    ///
+    /// Requires the `allocator_api` feature (nightly); see
+    /// [`try_boxed`](WrapExt::try_boxed).
+    #[cfg(all(feature = "allocator_api", feature = "sync"))]
     #[inline(always)]
-    fn arc_mutex(self) -> Arc<Mutex<Self>> {
-        Arc::new(Mutex::new(self))
+    fn try_arc(self) -> Result<Arc<Self>, core::alloc::AllocError> {
+        Arc::try_new(self)
     }
 
-    /// Wraps the value in an `Arc<RwLock<T>>`.
+    /// Wraps the value in an `Arc<T>` and returns two handles to it, e.g. one
+    /// to keep and one to move into a spawned thread.
     #[cfg(all(feature = "std", feature = "sync"))]
     #[inline(always)]
-    fn arc_rwlock(self) -> Arc<RwLock<Self>> {
-        Arc::new(RwLock::new(self))
+    fn arc_pair(self) -> (Arc<Self>, Arc<Self>) {
+        let arc = Arc::new(self);
+        (Arc::clone(&arc), arc)
     }
 
-    /// Wraps the value in a `OnceLock<T>`.
+    /// Wraps the value in an `ArcCow<T>`, an `Arc` that clones the value on
+    /// the first mutation while shared.
     #[cfg(all(feature = "std", feature = "sync"))]
     #[inline(always)]
-    fn once_lock(self) -> OnceLock<Self> {
-        OnceLock::from(self)
+    fn arc_cow(self) -> ArcCow<Self>
+    where
+        Self: Clone,
+    {
+        ArcCow::new(self)
     }
-}
 
-impl<T> WrapExt for T {}
+    /// Wraps the value in a `SmallArc<T>`, which skips the `Arc` allocation
+    /// when `T` fits in a pointer.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn small_arc(self) -> SmallArc<Self>
+    where
+        Self: Copy,
+    {
+        SmallArc::new(self)
+    }
 
-// ============================================================================
-// AtomicWrapExt Trait - Atomic Type Wrappers
-// ============================================================================
+    /// Wraps the value in an `Arc<Mutex<T>>`.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn arc_mutex(self) -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(self))
+    }
+
+    /// Wraps the value in an `Arc<Mutex<T>>` after running `init` on it.
+    ///
+    /// `init` gets exclusive access before any clone of the `Arc` exists, so
+    /// nothing can observe the value half-initialized. Since that access is
+    /// already exclusive, no lock is taken. Keeps construction and setup in
+    /// one expression instead of an `arc_mutex()` followed by a `lock()`.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline]
+    fn arc_mutex_init<F>(mut self, init: F) -> Arc<Mutex<Self>>
+    where
+        F: FnOnce(&mut Self),
+    {
+        init(&mut self);
+        Arc::new(Mutex::new(self))
+    }
+
+    /// Wraps the value in an `Arc<TrackedMutex<T>>`.
+    ///
+    /// With the `deadlock_debug` feature in a debug build, locking checks for
+    /// lock-order inversions between tracked mutexes; otherwise this is
+    /// [`arc_mutex`](WrapExt::arc_mutex).
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn tracked_mutex(self) -> Arc<TrackedMutex<Self>> {
+        Arc::new(TrackedMutex::new(self))
+    }
+
+    /// Wraps the value in an `Arc<RwLock<T>>`.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn arc_rwlock(self) -> Arc<RwLock<Self>> {
+        Arc::new(RwLock::new(self))
+    }
+
+    /// Wraps the value in an `Arc<PreferWriterRwLock<T>>`, an `RwLock` that
+    /// stops admitting new readers once a writer is waiting.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn arc_prefer_writer_rwlock(self) -> Arc<PreferWriterRwLock<Self>> {
+        Arc::new(PreferWriterRwLock::new(self))
+    }
+
+    /// Wraps the value in a `Monitor<T>`, a `Mutex<T>` paired with a
+    /// `Condvar` for waiting until the value reaches some state.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn monitor(self) -> Monitor<Self> {
+        Monitor::new(self)
+    }
+
+    /// Wraps the value in a `SwapSlot<T>`, an `Arc<T>` that can be replaced
+    /// while earlier loads keep the old value.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn swap_slot(self) -> SwapSlot<Self> {
+        SwapSlot::new(self)
+    }
+
+    /// Wraps the value in a `OnceLock<T>`.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn once_lock(self) -> OnceLock<Self> {
+        OnceLock::from(self)
+    }
+
+    /// Wraps the enum in an `AtomicEnum<E>`, stored as its discriminant.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn atomic_enum(self) -> AtomicEnum<Self>
+    where
+        Self: AtomicEnumRepr,
+    {
+        AtomicEnum::new(self)
+    }
+
+    /// Wraps the value in an `AtomicCell<T>`, lock-free when `T` is 1, 2, 4
+    /// or 8 bytes.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn atomic_cell(self) -> AtomicCell<Self>
+    where
+        Self: NoPadding,
+    {
+        AtomicCell::new(self)
+    }
+
+    /// Wraps the value in a `Pin<Arc<T>>`; the same as
+    /// [`pin_arc`](WrapExt::pin_arc).
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn arc_pin(self) -> Pin<Arc<Self>> {
+        Arc::pin(self)
+    }
+
+    /// Pins the value in an `Arc`, like `Arc::pin`.
+    ///
+    /// This is the thread-shareable member of the `Box::pin`/`Rc::pin`/
+    /// `Arc::pin` family: clones of the `Pin<Arc<T>>` all point at the same
+    /// pinned value, and for a `!Unpin` type none of them hands out `&mut T`
+    /// or the value itself, so it is never moved again.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn pin_arc(self) -> Pin<Arc<Self>> {
+        Arc::pin(self)
+    }
+
+    /// Wraps the value in a type-erased `Arc<dyn Any + Send + Sync>`.
+    ///
+    /// Recover the concrete type with [`downcast_arc`].
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn arc_any(self) -> Arc<dyn core::any::Any + Send + Sync>
+    where
+        Self: Send + Sync + 'static,
+    {
+        Arc::new(self)
+    }
+
+    /// Wraps the value in an `Arc<tokio::sync::Mutex<T>>`.
+    ///
+    /// Unlike the std mutex, the guard may be held across `.await` points.
+    #[cfg(feature = "tokio")]
+    #[inline(always)]
+    fn arc_tokio_mutex(self) -> std::sync::Arc<tokio::sync::Mutex<Self>> {
+        std::sync::Arc::new(tokio::sync::Mutex::new(self))
+    }
+
+    /// Wraps the value in an `Arc<tokio::sync::RwLock<T>>`.
+    ///
+    /// Unlike the std lock, guards may be held across `.await` points.
+    #[cfg(feature = "tokio")]
+    #[inline(always)]
+    fn arc_tokio_rwlock(self) -> std::sync::Arc<tokio::sync::RwLock<Self>> {
+        std::sync::Arc::new(tokio::sync::RwLock::new(self))
+    }
+
+    /// Wraps the value in an `Arc<parking_lot::RwLock<T>>`.
+    ///
+    /// Unlike the std lock it supports upgradable reads; see
+    /// [`UpgradableReadExt`].
+    #[cfg(feature = "parking_lot")]
+    #[inline(always)]
+    fn arc_pl_rwlock(self) -> Arc<parking_lot::RwLock<Self>> {
+        Arc::new(parking_lot::RwLock::new(self))
+    }
+
+    /// Wraps the value in a `spin::Mutex<T>`.
+    ///
+    /// Needs neither `std` nor `alloc`, so it works on bare-metal targets
+    /// where the std locks don't exist. Waiting threads spin rather than
+    /// sleep, so keep critical sections short.
+    #[cfg(feature = "spin")]
+    #[inline(always)]
+    fn spin_mutex(self) -> spin::Mutex<Self> {
+        spin::Mutex::new(self)
+    }
+
+    /// Wraps the value in a `spin::RwLock<T>`.
+    ///
+    /// The `no_std` counterpart of `RwLock`; see
+    /// [`spin_mutex`](WrapExt::spin_mutex).
+    #[cfg(feature = "spin")]
+    #[inline(always)]
+    fn spin_rwlock(self) -> spin::RwLock<Self> {
+        spin::RwLock::new(self)
+    }
+
+    /// Boxes the value as a `Box<dyn Error + Send + Sync>`.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn into_boxed_dyn_error(self) -> BoxError
+    where
+        Self: std::error::Error + Send + Sync + 'static,
+    {
+        Box::new(self)
+    }
+
+    /// Wraps the value as a boxed error whose `source()` returns `source`.
+    ///
+    /// The resulting error displays as the original value.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn into_dyn_error_with_source(self, source: BoxError) -> BoxError
+    where
+        Self: core::fmt::Debug + core::fmt::Display + Send + Sync + 'static,
+    {
+        Box::new(SourcedError::new(self, source))
+    }
+}
+
+impl<T> WrapExt for T {}
+
+// ============================================================================
+// Const Constructors
+// ============================================================================
+
+/// Creates a `Cell<T>` in a `const` context.
+///
+/// The const-evaluable counterpart of [`WrapExt::cell`]. `Cell` is not `Sync`,
+/// so it cannot back a plain `static`; use it in `const` blocks or
+/// const-initialized thread locals instead.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use std::cell::Cell;
+///     use autowrap::const_cell;
+///
+///     thread_local! {
+///         static HITS: Cell<u32> = const { const_cell(0) };
+///     }
+///
+///     HITS.set(HITS.get() + 1);
+///     assert_eq!(HITS.get(), 1);
+/// }
+/// ```
+#[inline(always)]
+pub const fn const_cell<T: Copy>(value: T) -> Cell<T> {
+    Cell::new(value)
+}
+
+/// Creates an `UnsafeCell<T>` in a `const` context.
+#[inline(always)]
+pub const fn const_unsafe_cell<T>(value: T) -> UnsafeCell<T> {
+    UnsafeCell::new(value)
+}
+
+// ============================================================================
+// AtomicWrapExt Trait - Atomic Type Wrappers
+// ============================================================================
 
 /// Extension trait providing atomic wrappers for integer types.
 ///
+/// Like the other atomic extension traits, this only needs `core`: the
+/// `sync` feature enables it without `std`, for `no_std` targets. The
+/// `atomic_u64`/`atomic_i64` constructors (and the 64-bit impls of those
+/// traits) are only present on targets with native 64-bit atomics, unless
+/// the `portable-atomic` feature is enabled.
+///
+/// # `portable-atomic`
+///
+/// With the `portable-atomic` feature, `atomic_u64`/`atomic_i64` return
+/// `portable_atomic::AtomicU64`/`AtomicI64` on every target, and
+/// `atomic_u128`/`atomic_i128` become available. Where the hardware lacks
+/// atomics of that width they are emulated, with a global lock table or,
+/// on single-core targets configured for it, by disabling interrupts. The
+/// types have the same method names and the same in-memory representation
+/// as the integer, but they are not `core`'s types:
+///
+/// - they can't be passed to APIs that expect
+///   `core::sync::atomic::AtomicU64`, and enabling the feature changes the
+///   return type of `atomic_u64`/`atomic_i64` everywhere in the build;
+/// - emulated operations are not lock-free, so they must not be mixed with
+///   accesses to the same memory from outside the program (FFI, shared
+///   memory, DMA) that assume native atomics;
+/// - on targets without native support, see the `portable-atomic` docs for
+///   the `critical-section` or `unsafe-assume-single-core` setup it needs.
+///
 /// # Performance
 ///
 /// All implementations use `#[inline(always)]` and generate optimal
@@ -146,7 +876,7 @@ impl<T> WrapExt for T {}
 ///     counter.fetch_add(1, core::sync::atomic::Ordering::SeqCst);
 /// }
 /// ```
-#[cfg(all(feature = "std", feature = "sync"))]
+#[cfg(feature = "sync")]
 #[allow(clippy::redundant_closure_call)]
 pub trait AtomicWrapExt {
     /// Creates an `AtomicUsize` from this value.
@@ -162,11 +892,26 @@ pub trait AtomicWrapExt {
     fn atomic_i32(self) -> AtomicI32;
 
     /// Creates an `AtomicU64` from this value.
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
     fn atomic_u64(self) -> AtomicU64;
 
     /// Creates an `AtomicI64` from this value.
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
     fn atomic_i64(self) -> AtomicI64;
 
+    /// Creates a `portable_atomic::AtomicU128` from this value.
+    ///
+    /// `core` has no 128-bit atomics on stable, so this is only provided
+    /// through the `portable-atomic` feature. It uses `cmpxchg16b` (x86-64)
+    /// or the equivalent where the CPU has it, and the lock-based fallback
+    /// otherwise.
+    #[cfg(feature = "portable-atomic")]
+    fn atomic_u128(self) -> AtomicU128;
+
+    /// Creates a `portable_atomic::AtomicI128` from this value.
+    #[cfg(feature = "portable-atomic")]
+    fn atomic_i128(self) -> AtomicI128;
+
     /// Creates an `AtomicBool` from this value.
     fn atomic_bool(self) -> AtomicBool;
 }
@@ -181,11 +926,13 @@ macro_rules! impl_atomic_wrap {
             i32: $to_i32:expr,
             u64: $to_u64:expr,
             i64: $to_i64:expr,
+            u128: $to_u128:expr,
+            i128: $to_i128:expr,
             bool: $to_bool:expr
         } ),* $(,)?
     ) => {
         $(
-            #[cfg(all(feature = "std", feature = "sync"))]
+            #[cfg(feature = "sync")]
             impl AtomicWrapExt for $ty {
                 #[inline(always)]
                 fn atomic_usize(self) -> AtomicUsize {
@@ -207,16 +954,30 @@ macro_rules! impl_atomic_wrap {
                     AtomicI32::new($to_i32(self))
                 }
 
+                #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
                 #[inline(always)]
                 fn atomic_u64(self) -> AtomicU64 {
                     AtomicU64::new($to_u64(self))
                 }
 
+                #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
                 #[inline(always)]
                 fn atomic_i64(self) -> AtomicI64 {
                     AtomicI64::new($to_i64(self))
                 }
 
+                #[cfg(feature = "portable-atomic")]
+                #[inline(always)]
+                fn atomic_u128(self) -> AtomicU128 {
+                    AtomicU128::new($to_u128(self))
+                }
+
+                #[cfg(feature = "portable-atomic")]
+                #[inline(always)]
+                fn atomic_i128(self) -> AtomicI128 {
+                    AtomicI128::new($to_i128(self))
+                }
+
                 #[inline(always)]
                 fn atomic_bool(self) -> AtomicBool {
                     AtomicBool::new($to_bool(self))
@@ -235,6 +996,8 @@ impl_atomic_wrap! {
         i32: |v: usize| v as i32,
         u64: |v: usize| v as u64,
         i64: |v: usize| v as i64,
+        u128: |v: usize| v as u128,
+        i128: |v: usize| v as i128,
         bool: |v: usize| v != 0
     },
     isize => {
@@ -244,6 +1007,8 @@ impl_atomic_wrap! {
         i32: |v: isize| v as i32,
         u64: |v: isize| v as u64,
         i64: |v: isize| v as i64,
+        u128: |v: isize| v as u128,
+        i128: |v: isize| v as i128,
         bool: |v: isize| v != 0
     },
     u32 => {
@@ -253,6 +1018,8 @@ impl_atomic_wrap! {
         i32: |v: u32| v as i32,
         u64: |v: u32| v as u64,
         i64: |v: u32| v as i64,
+        u128: |v: u32| v as u128,
+        i128: |v: u32| v as i128,
         bool: |v: u32| v != 0
     },
     i32 => {
@@ -262,6 +1029,8 @@ impl_atomic_wrap! {
         i32: |v: i32| v,
         u64: |v: i32| v as u64,
         i64: |v: i32| v as i64,
+        u128: |v: i32| v as u128,
+        i128: |v: i32| v as i128,
         bool: |v: i32| v != 0
     },
     u64 => {
@@ -271,6 +1040,8 @@ impl_atomic_wrap! {
         i32: |v: u64| v as i32,
         u64: |v: u64| v,
         i64: |v: u64| v as i64,
+        u128: |v: u64| v as u128,
+        i128: |v: u64| v as i128,
         bool: |v: u64| v != 0
     },
     i64 => {
@@ -280,8 +1051,32 @@ impl_atomic_wrap! {
         i32: |v: i64| v as i32,
         u64: |v: i64| v as u64,
         i64: |v: i64| v,
+        u128: |v: i64| v as u128,
+        i128: |v: i64| v as i128,
         bool: |v: i64| v != 0
     },
+    u128 => {
+        usize: |v: u128| v as usize,
+        isize: |v: u128| v as isize,
+        u32: |v: u128| v as u32,
+        i32: |v: u128| v as i32,
+        u64: |v: u128| v as u64,
+        i64: |v: u128| v as i64,
+        u128: |v: u128| v,
+        i128: |v: u128| v as i128,
+        bool: |v: u128| v != 0
+    },
+    i128 => {
+        usize: |v: i128| v as usize,
+        isize: |v: i128| v as isize,
+        u32: |v: i128| v as u32,
+        i32: |v: i128| v as i32,
+        u64: |v: i128| v as u64,
+        i64: |v: i128| v as i64,
+        u128: |v: i128| v as u128,
+        i128: |v: i128| v,
+        bool: |v: i128| v != 0
+    },
     bool => {
         usize: |v: bool| v as usize,
         isize: |v: bool| v as isize,
@@ -289,103 +1084,3333 @@ impl_atomic_wrap! {
         i32: |v: bool| v as i32,
         u64: |v: bool| v as u64,
         i64: |v: bool| v as i64,
+        u128: |v: bool| v as u128,
+        i128: |v: bool| v as i128,
         bool: |v: bool| v
+    },
+    char => {
+        usize: |v: char| v as usize,
+        isize: |v: char| v as isize,
+        u32: |v: char| v as u32,
+        i32: |v: char| v as i32,
+        u64: |v: char| v as u64,
+        i64: |v: char| v as i64,
+        u128: |v: char| v as u128,
+        i128: |v: char| v as i128,
+        bool: |v: char| v != '\0'
     }
 }
 
 // ============================================================================
-// Tests
+// CharAtomicExt Trait - char Storage in AtomicU32
 // ============================================================================
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Extension trait storing a `char` in an `AtomicU32` by its scalar value.
+///
+/// Pairs with `'x'.atomic_u32()`. Every `char` is a valid Unicode scalar
+/// value, so loading back what a `char` store wrote always succeeds. The
+/// atomic itself accepts any `u32`, though: mixing these methods with plain
+/// `store`/`fetch_add` calls can leave a value that is not a scalar (such as
+/// a surrogate), and [`load_char`](CharAtomicExt::load_char) panics on one.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::{AtomicWrapExt, CharAtomicExt};
+///     use core::sync::atomic::Ordering;
+///
+///     let separator = ','.atomic_u32();
+///     separator.store_char(';', Ordering::Relaxed);
+///     assert_eq!(separator.load_char(Ordering::Relaxed), ';');
+/// }
+/// ```
+#[cfg(feature = "sync")]
+pub trait CharAtomicExt {
+    /// Loads the stored `char`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stored value is not a Unicode scalar value.
+    fn load_char(&self, order: Ordering) -> char;
 
-    #[test]
-    fn test_cell_wrap() {
-        let c = 10u32.cell();
-        assert_eq!(c.get(), 10);
-        c.set(20);
-        assert_eq!(c.get(), 20);
-    }
+    /// Stores `value` as its scalar value.
+    fn store_char(&self, value: char, order: Ordering);
 
-    #[cfg(feature = "std")]
-    mod std_tests {
-        use super::*;
+    /// Stores `value` and returns the previous `char`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the previous value is not a Unicode scalar value; `value`
+    /// is stored regardless.
+    fn swap_char(&self, value: char, order: Ordering) -> char;
+}
 
-        #[test]
-        fn test_refcell_wrap() {
-            let r = 5u32.refcell();
-            *r.borrow_mut() = 8;
-            assert_eq!(*r.borrow(), 8);
-        }
+#[cfg(feature = "sync")]
+#[inline(always)]
+#[track_caller]
+fn scalar_to_char(scalar: u32) -> char {
+    match char::from_u32(scalar) {
+        Some(c) => c,
+        None => panic!("AtomicU32 holds {:#x}, which is not a Unicode scalar value", scalar),
+    }
+}
 
-        #[test]
-        fn test_rc_wrap() {
-            let rc = 42u32.rc();
-            assert_eq!(*rc, 42);
-            let rc2 = core::rc::Rc::clone(&rc);
-            assert_eq!(core::rc::Rc::strong_count(&rc2), 2);
-        }
+#[cfg(feature = "sync")]
+impl CharAtomicExt for AtomicU32 {
+    #[inline(always)]
+    fn load_char(&self, order: Ordering) -> char {
+        scalar_to_char(self.load(order))
+    }
 
-        #[test]
-        fn test_rc_refcell_wrap() {
-            let value = 100u32.rc_refcell();
-            *value.borrow_mut() = 200;
-            assert_eq!(*value.borrow(), 200);
-            let cloned = core::rc::Rc::clone(&value);
-            assert_eq!(core::rc::Rc::strong_count(&cloned), 2);
-        }
+    #[inline(always)]
+    fn store_char(&self, value: char, order: Ordering) {
+        self.store(value as u32, order)
+    }
 
-        #[cfg(feature = "sync")]
-        mod sync_tests {
-            use super::*;
-            use std::thread;
+    #[inline(always)]
+    fn swap_char(&self, value: char, order: Ordering) -> char {
+        scalar_to_char(self.swap(value as u32, order))
+    }
+}
 
-            #[test]
-            fn test_arc_wrap() {
-                let arc = 99u32.arc();
-                assert_eq!(*arc, 99);
-                let arc2 = std::sync::Arc::clone(&arc);
-                assert_eq!(std::sync::Arc::strong_count(&arc2), 2);
-            }
+// ============================================================================
+// AtomicOrdExt Trait - Ordering Presets for Integer Atomics
+// ============================================================================
 
-            #[test]
-            fn test_arc_mutex_wrap() {
-                let value = 1u32.arc_mutex();
-                {
-                    let mut lock = value.lock().unwrap();
-                    *lock = 10;
-                }
-                assert_eq!(*value.lock().unwrap(), 10);
+/// A load/store ordering pair for use with [`AtomicOrdExt`].
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::{AtomicOrdExt, AtomicWrapExt, Orderings};
+///
+///     let seq = 0u64.atomic_u64();
+///     seq.store_with(5, Orderings::ACQ_REL);
+///     assert_eq!(seq.load_with(Orderings::ACQ_REL), 5);
+/// }
+/// ```
+#[cfg(feature = "sync")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Orderings {
+    /// Ordering used for loads.
+    pub load: Ordering,
+    /// Ordering used for stores.
+    pub store: Ordering,
+}
 
-                let cloned = std::sync::Arc::clone(&value);
-                let handle = thread::spawn(move || {
-                    let mut data = cloned.lock().unwrap();
-                    *data += 5;
-                });
-                handle.join().unwrap();
-                assert_eq!(*value.lock().unwrap(), 15);
-            }
+#[cfg(feature = "sync")]
+impl Orderings {
+    /// `Acquire` loads paired with `Release` stores.
+    pub const ACQ_REL: Orderings = Orderings::new(Ordering::Acquire, Ordering::Release);
 
-            #[test]
-            fn test_arc_rwlock_wrap() {
-                let value = 1u32.arc_rwlock();
-                {
-                    let mut write = value.write().unwrap();
-                    *write = 10;
-                }
-                assert_eq!(*value.read().unwrap(), 10);
-            }
+    /// `Relaxed` loads and stores, for counters with no ordering requirements.
+    pub const RELAXED: Orderings = Orderings::new(Ordering::Relaxed, Ordering::Relaxed);
 
-            #[test]
-            fn test_once_lock_wrap() {
-                let once = 42u32.once_lock();
-                assert_eq!(*once.get().unwrap(), 42);
-            }
+    /// `SeqCst` loads and stores.
+    pub const SEQ_CST: Orderings = Orderings::new(Ordering::SeqCst, Ordering::SeqCst);
 
-            #[test]
-            fn test_atomic_wrappers() {
+    /// Creates a custom pair.
+    ///
+    /// `load` must not be `Release`/`AcqRel` and `store` must not be
+    /// `Acquire`/`AcqRel`, or the atomic operations will panic.
+    #[inline(always)]
+    pub const fn new(load: Ordering, store: Ordering) -> Self {
+        Orderings { load, store }
+    }
+}
+
+/// Named-ordering shorthands for the integer atomics.
+///
+/// `load_acq`/`store_rel` give the usual publish/consume pairing, and the
+/// `_relaxed` variants suit statistics counters. Each method is a direct call
+/// to the underlying atomic operation with the ordering in its name.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::{AtomicOrdExt, AtomicWrapExt};
+///
+///     let hits = 0u32.atomic_u32();
+///     hits.fetch_add_relaxed(1);
+///     hits.store_rel(10);
+///     assert_eq!(hits.load_acq(), 10);
+/// }
+/// ```
+#[cfg(feature = "sync")]
+pub trait AtomicOrdExt {
+    /// The integer type stored in the atomic.
+    type Value;
+
+    /// Loads with `Acquire`.
+    fn load_acq(&self) -> Self::Value;
+
+    /// Loads with `Relaxed`.
+    fn load_relaxed(&self) -> Self::Value;
+
+    /// Loads with `orderings.load`.
+    fn load_with(&self, orderings: Orderings) -> Self::Value;
+
+    /// Stores with `Release`.
+    fn store_rel(&self, value: Self::Value);
+
+    /// Stores with `Relaxed`.
+    fn store_relaxed(&self, value: Self::Value);
+
+    /// Stores with `orderings.store`.
+    fn store_with(&self, value: Self::Value, orderings: Orderings);
+
+    /// Swaps with `AcqRel`.
+    fn swap_acq_rel(&self, value: Self::Value) -> Self::Value;
+
+    /// Adds with `Relaxed`, returning the previous value.
+    fn fetch_add_relaxed(&self, value: Self::Value) -> Self::Value;
+
+    /// Adds with `AcqRel`, returning the previous value.
+    fn fetch_add_acq_rel(&self, value: Self::Value) -> Self::Value;
+
+    /// Subtracts with `Relaxed`, returning the previous value.
+    fn fetch_sub_relaxed(&self, value: Self::Value) -> Self::Value;
+
+    /// Subtracts with `AcqRel`, returning the previous value.
+    fn fetch_sub_acq_rel(&self, value: Self::Value) -> Self::Value;
+}
+
+macro_rules! impl_atomic_ord {
+    ($($(#[$attr:meta])* $atomic:ty => $ty:ty),* $(,)?) => {
+        $(
+            #[cfg(feature = "sync")]
+            $(#[$attr])*
+            impl AtomicOrdExt for $atomic {
+                type Value = $ty;
+
+                #[inline(always)]
+                fn load_acq(&self) -> $ty {
+                    self.load(Ordering::Acquire)
+                }
+
+                #[inline(always)]
+                fn load_relaxed(&self) -> $ty {
+                    self.load(Ordering::Relaxed)
+                }
+
+                #[inline(always)]
+                fn load_with(&self, orderings: Orderings) -> $ty {
+                    self.load(orderings.load)
+                }
+
+                #[inline(always)]
+                fn store_rel(&self, value: $ty) {
+                    self.store(value, Ordering::Release)
+                }
+
+                #[inline(always)]
+                fn store_relaxed(&self, value: $ty) {
+                    self.store(value, Ordering::Relaxed)
+                }
+
+                #[inline(always)]
+                fn store_with(&self, value: $ty, orderings: Orderings) {
+                    self.store(value, orderings.store)
+                }
+
+                #[inline(always)]
+                fn swap_acq_rel(&self, value: $ty) -> $ty {
+                    self.swap(value, Ordering::AcqRel)
+                }
+
+                #[inline(always)]
+                fn fetch_add_relaxed(&self, value: $ty) -> $ty {
+                    self.fetch_add(value, Ordering::Relaxed)
+                }
+
+                #[inline(always)]
+                fn fetch_add_acq_rel(&self, value: $ty) -> $ty {
+                    self.fetch_add(value, Ordering::AcqRel)
+                }
+
+                #[inline(always)]
+                fn fetch_sub_relaxed(&self, value: $ty) -> $ty {
+                    self.fetch_sub(value, Ordering::Relaxed)
+                }
+
+                #[inline(always)]
+                fn fetch_sub_acq_rel(&self, value: $ty) -> $ty {
+                    self.fetch_sub(value, Ordering::AcqRel)
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_ord! {
+    core::sync::atomic::AtomicU8 => u8,
+    core::sync::atomic::AtomicU16 => u16,
+    AtomicU32 => u32,
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))] AtomicU64 => u64,
+    #[cfg(feature = "portable-atomic")] AtomicU128 => u128,
+    AtomicUsize => usize,
+    core::sync::atomic::AtomicI8 => i8,
+    core::sync::atomic::AtomicI16 => i16,
+    AtomicI32 => i32,
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))] AtomicI64 => i64,
+    #[cfg(feature = "portable-atomic")] AtomicI128 => i128,
+    AtomicIsize => isize,
+}
+
+// ============================================================================
+// AtomicCasExt Trait - Single-Ordering Compare-and-Swap
+// ============================================================================
+
+/// Compare-and-swap on the integer atomics with the orderings chosen for you.
+///
+/// | Method | Success | Failure |
+/// |--------|---------|---------|
+/// | [`cas`](AtomicCasExt::cas) | `AcqRel` | `Acquire` |
+/// | [`cas_weak`](AtomicCasExt::cas_weak) | `Release` | `Relaxed` |
+///
+/// `cas` is the safe default: a success both publishes this thread's writes
+/// and observes the previous writer's, and a failure still observes the
+/// writer of the value it returns. `cas_weak` is meant for retry loops that
+/// only publish, such as bumping a counter whose previous contents don't
+/// need to be read; it may fail spuriously, so always call it in a loop.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::{AtomicCasExt, AtomicWrapExt};
+///
+///     let state = 1u32.atomic_u32();
+///     assert_eq!(state.cas(1, 2), Ok(1));
+///     assert_eq!(state.cas(1, 3), Err(2));
+/// }
+/// ```
+#[cfg(feature = "sync")]
+pub trait AtomicCasExt {
+    /// The integer type stored in the atomic.
+    type Value;
+
+    /// Stores `new` if the value equals `current`
+    /// (`AcqRel` on success, `Acquire` on failure).
+    ///
+    /// Returns the previous value: `Ok` if it was replaced, `Err` otherwise.
+    fn cas(&self, current: Self::Value, new: Self::Value) -> Result<Self::Value, Self::Value>;
+
+    /// Like [`cas`](AtomicCasExt::cas) but may fail spuriously
+    /// (`Release` on success, `Relaxed` on failure).
+    fn cas_weak(&self, current: Self::Value, new: Self::Value) -> Result<Self::Value, Self::Value>;
+}
+
+macro_rules! impl_atomic_cas {
+    ($($(#[$attr:meta])* $atomic:ty => $ty:ty),* $(,)?) => {
+        $(
+            #[cfg(feature = "sync")]
+            $(#[$attr])*
+            impl AtomicCasExt for $atomic {
+                type Value = $ty;
+
+                #[inline(always)]
+                fn cas(&self, current: $ty, new: $ty) -> Result<$ty, $ty> {
+                    self.compare_exchange(current, new, Ordering::AcqRel, Ordering::Acquire)
+                }
+
+                #[inline(always)]
+                fn cas_weak(&self, current: $ty, new: $ty) -> Result<$ty, $ty> {
+                    self.compare_exchange_weak(current, new, Ordering::Release, Ordering::Relaxed)
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_cas! {
+    core::sync::atomic::AtomicU8 => u8,
+    core::sync::atomic::AtomicU16 => u16,
+    AtomicU32 => u32,
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))] AtomicU64 => u64,
+    #[cfg(feature = "portable-atomic")] AtomicU128 => u128,
+    AtomicUsize => usize,
+    core::sync::atomic::AtomicI8 => i8,
+    core::sync::atomic::AtomicI16 => i16,
+    AtomicI32 => i32,
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))] AtomicI64 => i64,
+    #[cfg(feature = "portable-atomic")] AtomicI128 => i128,
+    AtomicIsize => isize,
+}
+
+// ============================================================================
+// AtomicMinMaxExt Trait - Relaxed High/Low-Water Marks
+// ============================================================================
+
+/// `fetch_max`/`fetch_min` on the integer atomics with `Relaxed` ordering.
+///
+/// Meant for statistics such as peak memory or worst-case latency, where the
+/// mark itself is the only data and nothing else is published alongside it.
+/// Reach for `fetch_max` with an explicit ordering if other memory must be
+/// synchronized through the mark.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::{AtomicMinMaxExt, AtomicWrapExt};
+///
+///     let peak = 10u64.atomic_u64();
+///     assert_eq!(peak.max(25), 10);
+///     assert_eq!(peak.max(20), 25);
+///     assert_eq!(peak.min(5), 25);
+/// }
+/// ```
+#[cfg(feature = "sync")]
+pub trait AtomicMinMaxExt {
+    /// The integer type stored in the atomic.
+    type Value;
+
+    /// Raises the value to `value` if it is larger, returning the previous
+    /// value (`Relaxed`).
+    fn max(&self, value: Self::Value) -> Self::Value;
+
+    /// Lowers the value to `value` if it is smaller, returning the previous
+    /// value (`Relaxed`).
+    fn min(&self, value: Self::Value) -> Self::Value;
+}
+
+macro_rules! impl_atomic_min_max {
+    ($($(#[$attr:meta])* $atomic:ty => $ty:ty),* $(,)?) => {
+        $(
+            #[cfg(feature = "sync")]
+            $(#[$attr])*
+            impl AtomicMinMaxExt for $atomic {
+                type Value = $ty;
+
+                #[inline(always)]
+                fn max(&self, value: $ty) -> $ty {
+                    self.fetch_max(value, Ordering::Relaxed)
+                }
+
+                #[inline(always)]
+                fn min(&self, value: $ty) -> $ty {
+                    self.fetch_min(value, Ordering::Relaxed)
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_min_max! {
+    core::sync::atomic::AtomicU8 => u8,
+    core::sync::atomic::AtomicU16 => u16,
+    AtomicU32 => u32,
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))] AtomicU64 => u64,
+    #[cfg(feature = "portable-atomic")] AtomicU128 => u128,
+    AtomicUsize => usize,
+    core::sync::atomic::AtomicI8 => i8,
+    core::sync::atomic::AtomicI16 => i16,
+    AtomicI32 => i32,
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))] AtomicI64 => i64,
+    #[cfg(feature = "portable-atomic")] AtomicI128 => i128,
+    AtomicIsize => isize,
+}
+
+// ============================================================================
+// IntoInnerExt Trait - Atomic Teardown
+// ============================================================================
+
+/// Extension trait consuming an atomic and returning its value.
+///
+/// [`into_value`](IntoInnerExt::into_value) is `into_inner` under a name that
+/// reads as the inverse of the [`AtomicWrapExt`] constructors. Owning the
+/// atomic proves no other thread can still access it, so no ordering is
+/// involved. A `From<AtomicU32> for u32` impl would be the other natural
+/// spelling, but both types are foreign to this crate, so it can't be
+/// provided here.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::{AtomicWrapExt, IntoInnerExt};
+///     use std::sync::atomic::Ordering;
+///
+///     let hits = 0u32.atomic_u32();
+///     hits.fetch_add(3, Ordering::Relaxed);
+///     assert_eq!(hits.into_value(), 3);
+/// }
+/// ```
+#[cfg(feature = "sync")]
+pub trait IntoInnerExt {
+    /// The value type stored in the atomic.
+    type Value;
+
+    /// Consumes the atomic, returning its value.
+    fn into_value(self) -> Self::Value;
+}
+
+macro_rules! impl_into_inner {
+    ($($(#[$attr:meta])* $atomic:ty => $ty:ty),* $(,)?) => {
+        $(
+            #[cfg(feature = "sync")]
+            $(#[$attr])*
+            impl IntoInnerExt for $atomic {
+                type Value = $ty;
+
+                #[inline(always)]
+                fn into_value(self) -> $ty {
+                    self.into_inner()
+                }
+            }
+        )*
+    };
+}
+
+impl_into_inner! {
+    core::sync::atomic::AtomicU8 => u8,
+    core::sync::atomic::AtomicU16 => u16,
+    AtomicU32 => u32,
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))] AtomicU64 => u64,
+    #[cfg(feature = "portable-atomic")] AtomicU128 => u128,
+    AtomicUsize => usize,
+    core::sync::atomic::AtomicI8 => i8,
+    core::sync::atomic::AtomicI16 => i16,
+    AtomicI32 => i32,
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))] AtomicI64 => i64,
+    #[cfg(feature = "portable-atomic")] AtomicI128 => i128,
+    AtomicIsize => isize,
+    AtomicBool => bool,
+}
+
+#[cfg(feature = "sync")]
+impl<T> IntoInnerExt for AtomicPtr<T> {
+    type Value = *mut T;
+
+    #[inline(always)]
+    fn into_value(self) -> *mut T {
+        self.into_inner()
+    }
+}
+
+// ============================================================================
+// SaturatingAtomicWrapExt Trait - Clamping Atomic Constructors
+// ============================================================================
+
+/// Extension trait creating atomics from integers, saturating out-of-range
+/// values to the target's bounds.
+///
+/// Where the `AtomicWrapExt` constructors truncate with `as`, these clamp:
+/// `300u32.sat_atomic_u8()` holds `255` and `-5i32.sat_atomic_u32()` holds
+/// `0`. That suits gauge-style metrics where a wrapped value is nonsense.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::SaturatingAtomicWrapExt;
+///     use core::sync::atomic::Ordering;
+///
+///     assert_eq!(300u32.sat_atomic_u8().load(Ordering::Relaxed), u8::MAX);
+///     assert_eq!((-1i64).sat_atomic_usize().load(Ordering::Relaxed), 0);
+///     assert_eq!(u64::MAX.sat_atomic_i32().load(Ordering::Relaxed), i32::MAX);
+/// }
+/// ```
+#[cfg(feature = "sync")]
+pub trait SaturatingAtomicWrapExt {
+    /// Creates an `AtomicU8`, clamping to `0..=u8::MAX`.
+    fn sat_atomic_u8(self) -> core::sync::atomic::AtomicU8;
+
+    /// Creates an `AtomicU16`, clamping to `0..=u16::MAX`.
+    fn sat_atomic_u16(self) -> core::sync::atomic::AtomicU16;
+
+    /// Creates an `AtomicU32`, clamping to `0..=u32::MAX`.
+    fn sat_atomic_u32(self) -> AtomicU32;
+
+    /// Creates an `AtomicU64`, clamping to `0..=u64::MAX`.
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+    fn sat_atomic_u64(self) -> AtomicU64;
+
+    /// Creates an `AtomicUsize`, clamping to `0..=usize::MAX`.
+    fn sat_atomic_usize(self) -> AtomicUsize;
+
+    /// Creates an `AtomicI8`, clamping to `i8::MIN..=i8::MAX`.
+    fn sat_atomic_i8(self) -> core::sync::atomic::AtomicI8;
+
+    /// Creates an `AtomicI16`, clamping to `i16::MIN..=i16::MAX`.
+    fn sat_atomic_i16(self) -> core::sync::atomic::AtomicI16;
+
+    /// Creates an `AtomicI32`, clamping to `i32::MIN..=i32::MAX`.
+    fn sat_atomic_i32(self) -> AtomicI32;
+
+    /// Creates an `AtomicI64`, clamping to `i64::MIN..=i64::MAX`.
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+    fn sat_atomic_i64(self) -> AtomicI64;
+
+    /// Creates an `AtomicIsize`, clamping to `isize::MIN..=isize::MAX`.
+    fn sat_atomic_isize(self) -> AtomicIsize;
+}
+
+// Every supported integer fits in `i128`, so clamping there is exact.
+#[cfg(feature = "sync")]
+macro_rules! saturate {
+    ($value:expr, $target:ty) => {
+        ($value as i128).clamp(<$target>::MIN as i128, <$target>::MAX as i128) as $target
+    };
+}
+
+macro_rules! impl_sat_atomic_wrap {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            #[cfg(feature = "sync")]
+            impl SaturatingAtomicWrapExt for $ty {
+                #[inline(always)]
+                fn sat_atomic_u8(self) -> core::sync::atomic::AtomicU8 {
+                    core::sync::atomic::AtomicU8::new(saturate!(self, u8))
+                }
+
+                #[inline(always)]
+                fn sat_atomic_u16(self) -> core::sync::atomic::AtomicU16 {
+                    core::sync::atomic::AtomicU16::new(saturate!(self, u16))
+                }
+
+                #[inline(always)]
+                fn sat_atomic_u32(self) -> AtomicU32 {
+                    AtomicU32::new(saturate!(self, u32))
+                }
+
+                #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+                #[inline(always)]
+                fn sat_atomic_u64(self) -> AtomicU64 {
+                    AtomicU64::new(saturate!(self, u64))
+                }
+
+                #[inline(always)]
+                fn sat_atomic_usize(self) -> AtomicUsize {
+                    AtomicUsize::new(saturate!(self, usize))
+                }
+
+                #[inline(always)]
+                fn sat_atomic_i8(self) -> core::sync::atomic::AtomicI8 {
+                    core::sync::atomic::AtomicI8::new(saturate!(self, i8))
+                }
+
+                #[inline(always)]
+                fn sat_atomic_i16(self) -> core::sync::atomic::AtomicI16 {
+                    core::sync::atomic::AtomicI16::new(saturate!(self, i16))
+                }
+
+                #[inline(always)]
+                fn sat_atomic_i32(self) -> AtomicI32 {
+                    AtomicI32::new(saturate!(self, i32))
+                }
+
+                #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+                #[inline(always)]
+                fn sat_atomic_i64(self) -> AtomicI64 {
+                    AtomicI64::new(saturate!(self, i64))
+                }
+
+                #[inline(always)]
+                fn sat_atomic_isize(self) -> AtomicIsize {
+                    AtomicIsize::new(saturate!(self, isize))
+                }
+            }
+        )*
+    };
+}
+
+impl_sat_atomic_wrap!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+// ============================================================================
+// PodAtomicExt / AtomicPodExt Traits - Plain-Old-Data Atomic Storage
+// ============================================================================
+
+/// Extension trait storing an 8-byte `bytemuck::Pod` value in an `AtomicU64`.
+///
+/// The value's bytes are reinterpreted through `bytemuck::cast`, which is
+/// sound for any `Pod` type. Read the value back with
+/// [`AtomicPodExt::load_pod`].
+///
+/// # Panics
+///
+/// Panics if `size_of::<Self>() != 8`.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(all(feature = "sync", feature = "bytemuck"))]
+/// {
+///     use autowrap::{AtomicPodExt, PodAtomicExt};
+///     use core::sync::atomic::Ordering;
+///
+///     let bits = [1u16, 2, 3, 4].atomic_bits_u64();
+///     bits.store_pod([5u16, 6, 7, 8], Ordering::Release);
+///     assert_eq!(bits.load_pod::<[u16; 4]>(Ordering::Acquire), [5, 6, 7, 8]);
+/// }
+/// ```
+#[cfg(all(feature = "sync", feature = "bytemuck", any(target_has_atomic = "64", feature = "portable-atomic")))]
+pub trait PodAtomicExt: bytemuck::Pod {
+    /// Creates an `AtomicU64` holding this value's bytes.
+    fn atomic_bits_u64(self) -> AtomicU64;
+}
+
+#[cfg(all(feature = "sync", feature = "bytemuck", any(target_has_atomic = "64", feature = "portable-atomic")))]
+impl<T: bytemuck::Pod> PodAtomicExt for T {
+    #[inline(always)]
+    fn atomic_bits_u64(self) -> AtomicU64 {
+        AtomicU64::new(bytemuck::cast(self))
+    }
+}
+
+/// Typed `bytemuck::Pod` access to the bits of an `AtomicU64`.
+///
+/// # Panics
+///
+/// Every method panics if `size_of::<T>() != 8`.
+#[cfg(all(feature = "sync", feature = "bytemuck", any(target_has_atomic = "64", feature = "portable-atomic")))]
+pub trait AtomicPodExt {
+    /// Loads the bits and reinterprets them as `T`.
+    fn load_pod<T: bytemuck::Pod>(&self, order: Ordering) -> T;
+
+    /// Stores the bytes of `value`.
+    fn store_pod<T: bytemuck::Pod>(&self, value: T, order: Ordering);
+
+    /// Stores the bytes of `value` and returns the previous bits as `T`.
+    fn swap_pod<T: bytemuck::Pod>(&self, value: T, order: Ordering) -> T;
+}
+
+#[cfg(all(feature = "sync", feature = "bytemuck", any(target_has_atomic = "64", feature = "portable-atomic")))]
+impl AtomicPodExt for AtomicU64 {
+    #[inline(always)]
+    fn load_pod<T: bytemuck::Pod>(&self, order: Ordering) -> T {
+        bytemuck::cast(self.load(order))
+    }
+
+    #[inline(always)]
+    fn store_pod<T: bytemuck::Pod>(&self, value: T, order: Ordering) {
+        self.store(bytemuck::cast(value), order)
+    }
+
+    #[inline(always)]
+    fn swap_pod<T: bytemuck::Pod>(&self, value: T, order: Ordering) -> T {
+        bytemuck::cast(self.swap(bytemuck::cast(value), order))
+    }
+}
+
+// ============================================================================
+// AtomicPtrWrapExt Trait - Atomic Pointer Wrappers
+// ============================================================================
+
+/// Extension trait wrapping raw pointers into `AtomicPtr<T>`.
+///
+/// Null pointers are valid inputs: the resulting atomic simply loads null.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::{AtomicPtrExt, AtomicPtrWrapExt};
+///     use core::sync::atomic::Ordering;
+///
+///     let raw = Box::into_raw(Box::new(7u32));
+///     let ptr = core::ptr::null_mut::<u32>().atomic_ptr();
+///     assert!(ptr.swap_ptr(raw).is_null());
+///
+///     let back = unsafe { Box::from_raw(ptr.load(Ordering::Acquire)) };
+///     assert_eq!(*back, 7);
+/// }
+/// ```
+#[cfg(feature = "sync")]
+pub trait AtomicPtrWrapExt<T> {
+    /// Creates an `AtomicPtr<T>` from this pointer.
+    fn atomic_ptr(self) -> AtomicPtr<T>;
+}
+
+#[cfg(feature = "sync")]
+impl<T> AtomicPtrWrapExt<T> for *mut T {
+    #[inline(always)]
+    fn atomic_ptr(self) -> AtomicPtr<T> {
+        AtomicPtr::new(self)
+    }
+}
+
+/// Default-ordering helpers for `AtomicPtr<T>`.
+///
+/// Successful operations use `AcqRel` so that the pointee written before a
+/// publish is visible to the thread that observes the new pointer; a failed
+/// compare-exchange uses `Acquire`.
+#[cfg(feature = "sync")]
+pub trait AtomicPtrExt<T> {
+    /// Stores `new` and returns the previous pointer (`AcqRel`).
+    fn swap_ptr(&self, new: *mut T) -> *mut T;
+
+    /// Stores `new` if the current pointer equals `current`
+    /// (`AcqRel` on success, `Acquire` on failure).
+    fn compare_exchange_ptr(&self, current: *mut T, new: *mut T) -> Result<*mut T, *mut T>;
+}
+
+#[cfg(feature = "sync")]
+impl<T> AtomicPtrExt<T> for AtomicPtr<T> {
+    #[inline(always)]
+    fn swap_ptr(&self, new: *mut T) -> *mut T {
+        self.swap(new, Ordering::AcqRel)
+    }
+
+    #[inline(always)]
+    fn compare_exchange_ptr(&self, current: *mut T, new: *mut T) -> Result<*mut T, *mut T> {
+        self.compare_exchange(current, new, Ordering::AcqRel, Ordering::Acquire)
+    }
+}
+
+// ============================================================================
+// ArrayAtomicExt Trait - Atomic Array Wrappers
+// ============================================================================
+
+/// Extension trait mapping `[T; N]` into `[AtomicT; N]` element by element.
+///
+/// Implemented for arrays of every integer type and `bool`. The conversion
+/// goes through `array::map`, so no partially initialized array is ever
+/// observable.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::ArrayAtomicExt;
+///     use core::sync::atomic::Ordering;
+///
+///     let slots = [0u64; 4].atomic_array();
+///     slots[2].store(7, Ordering::Relaxed);
+///     assert_eq!(slots[2].load(Ordering::Relaxed), 7);
+/// }
+/// ```
+#[cfg(feature = "sync")]
+pub trait ArrayAtomicExt {
+    /// The array of atomics produced by [`atomic_array`](ArrayAtomicExt::atomic_array).
+    type Atomic;
+
+    /// Wraps each element in its corresponding atomic type.
+    fn atomic_array(self) -> Self::Atomic;
+}
+
+macro_rules! impl_array_atomic {
+    ($($(#[$attr:meta])* $ty:ty => $atomic:ty),* $(,)?) => {
+        $(
+            #[cfg(feature = "sync")]
+            $(#[$attr])*
+            impl<const N: usize> ArrayAtomicExt for [$ty; N] {
+                type Atomic = [$atomic; N];
+
+                #[inline(always)]
+                fn atomic_array(self) -> [$atomic; N] {
+                    self.map(<$atomic>::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_array_atomic! {
+    u8 => core::sync::atomic::AtomicU8,
+    u16 => core::sync::atomic::AtomicU16,
+    u32 => AtomicU32,
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))] u64 => AtomicU64,
+    #[cfg(feature = "portable-atomic")] u128 => AtomicU128,
+    usize => AtomicUsize,
+    i8 => core::sync::atomic::AtomicI8,
+    i16 => core::sync::atomic::AtomicI16,
+    i32 => AtomicI32,
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))] i64 => AtomicI64,
+    #[cfg(feature = "portable-atomic")] i128 => AtomicI128,
+    isize => AtomicIsize,
+    bool => AtomicBool,
+}
+
+// ============================================================================
+// ArrayBoxExt Trait - Array to Shared Slice Wrappers
+// ============================================================================
+
+/// Extension trait moving a `[T; N]` into a `Box<[T]>`, `Rc<[T]>` or
+/// `Arc<[T]>`.
+///
+/// Each method makes a single allocation of exactly `N` elements and moves
+/// the array into it, then unsizes the pointer; the length is carried in the
+/// fat pointer rather than the type.
+///
+/// The array is moved, so it must already exist as a value before the call,
+/// typically on the stack. For tables too large for the stack, build the heap
+/// slice directly instead, e.g. with `vec![x; n].into_boxed_slice()` or
+/// [`WrapExt::boxed_slice_repeat`]. The optimizer may elide the stack copy,
+/// but nothing guarantees it.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::ArrayBoxExt;
+///     use std::sync::Arc;
+///
+///     let table: Box<[u8]> = [1, 2, 4, 8].boxed_slice();
+///     assert_eq!(table.len(), 4);
+///
+///     let shared: Arc<[&str]> = ["a", "b"].arc_slice();
+///     assert_eq!(&*shared, ["a", "b"]);
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub trait ArrayBoxExt<T> {
+    /// Moves the array into a `Box<[T]>`.
+    fn boxed_slice(self) -> Box<[T]>;
+
+    /// Moves the array into an `Rc<[T]>`.
+    fn rc_slice(self) -> Rc<[T]>;
+
+    /// Moves the array into an `Arc<[T]>`.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    fn arc_slice(self) -> Arc<[T]>;
+}
+
+#[cfg(feature = "std")]
+impl<T, const N: usize> ArrayBoxExt<T> for [T; N] {
+    #[inline(always)]
+    fn boxed_slice(self) -> Box<[T]> {
+        Box::new(self)
+    }
+
+    #[inline(always)]
+    fn rc_slice(self) -> Rc<[T]> {
+        Rc::new(self)
+    }
+
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn arc_slice(self) -> Arc<[T]> {
+        Arc::new(self)
+    }
+}
+
+// ============================================================================
+// DurationWrapExt / InstantWrapExt Traits - Atomic Time Wrappers
+// ============================================================================
+
+/// Extension trait wrapping a `Duration` into an [`AtomicDuration`].
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::DurationWrapExt;
+///     use core::sync::atomic::Ordering;
+///     use std::time::Duration;
+///
+///     let timeout = Duration::from_secs(30).atomic_duration();
+///     timeout.store(Duration::from_secs(5), Ordering::Relaxed);
+///     assert_eq!(timeout.load(Ordering::Relaxed), Duration::from_secs(5));
+/// }
+/// ```
+#[cfg(all(feature = "std", feature = "sync", any(target_has_atomic = "64", feature = "portable-atomic")))]
+pub trait DurationWrapExt {
+    /// Creates an `AtomicDuration`, saturating beyond `u64::MAX` nanoseconds.
+    fn atomic_duration(self) -> AtomicDuration;
+}
+
+#[cfg(all(feature = "std", feature = "sync", any(target_has_atomic = "64", feature = "portable-atomic")))]
+impl DurationWrapExt for Duration {
+    #[inline(always)]
+    fn atomic_duration(self) -> AtomicDuration {
+        AtomicDuration::new(self)
+    }
+}
+
+/// Extension trait wrapping an `Instant` into an [`AtomicInstant`].
+#[cfg(all(feature = "std", feature = "sync", any(target_has_atomic = "64", feature = "portable-atomic")))]
+pub trait InstantWrapExt {
+    /// Creates an `AtomicInstant` using this instant as its base.
+    fn atomic_instant(self) -> AtomicInstant;
+}
+
+#[cfg(all(feature = "std", feature = "sync", any(target_has_atomic = "64", feature = "portable-atomic")))]
+impl InstantWrapExt for Instant {
+    #[inline(always)]
+    fn atomic_instant(self) -> AtomicInstant {
+        AtomicInstant::new(self)
+    }
+}
+
+// ============================================================================
+// NonZeroWrapExt Trait - NonZero Integer Wrappers
+// ============================================================================
+
+/// Extension trait converting integers into their `NonZero*` counterparts.
+///
+/// # Example
+///
+/// ```
+/// use autowrap::NonZeroWrapExt;
+///
+/// assert!(0u32.non_zero().is_none());
+/// assert_eq!(5u32.non_zero().map(|n| n.get()), Some(5));
+/// ```
+pub trait NonZeroWrapExt: Sized {
+    /// The `NonZero*` type for this integer.
+    type NonZero;
+
+    /// Returns the value as its `NonZero*` type, or `None` if it is zero.
+    fn non_zero(self) -> Option<Self::NonZero>;
+
+    /// Returns the value as its `NonZero*` type without checking for zero.
+    ///
+    /// # Safety
+    ///
+    /// The value must not be zero.
+    unsafe fn non_zero_unchecked(self) -> Self::NonZero;
+}
+
+macro_rules! impl_non_zero_wrap {
+    ( $( $ty:ty => $nz:ty ),* $(,)? ) => {
+        $(
+            impl NonZeroWrapExt for $ty {
+                type NonZero = $nz;
+
+                #[inline(always)]
+                fn non_zero(self) -> Option<$nz> {
+                    <$nz>::new(self)
+                }
+
+                #[inline(always)]
+                unsafe fn non_zero_unchecked(self) -> $nz {
+                    // SAFETY: the caller guarantees `self != 0`.
+                    unsafe { <$nz>::new_unchecked(self) }
+                }
+            }
+        )*
+    };
+}
+
+impl_non_zero_wrap! {
+    u8 => core::num::NonZeroU8,
+    u16 => core::num::NonZeroU16,
+    u32 => core::num::NonZeroU32,
+    u64 => core::num::NonZeroU64,
+    usize => core::num::NonZeroUsize,
+    i8 => core::num::NonZeroI8,
+    i16 => core::num::NonZeroI16,
+    i32 => core::num::NonZeroI32,
+    i64 => core::num::NonZeroI64,
+    isize => core::num::NonZeroIsize,
+}
+
+// ============================================================================
+// CowWrapExt / CowBorrowedExt Traits - Cow Construction
+// ============================================================================
+
+/// Extension trait wrapping owned values into `Cow::Owned`.
+///
+/// The borrowed type is taken from the value's `Deref` target, so a `String`
+/// becomes a `Cow<str>` and a `Vec<T>` becomes a `Cow<[T]>`.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use std::borrow::Cow;
+///     use autowrap::{CowBorrowedExt, CowWrapExt};
+///
+///     let owned: Cow<str> = String::from("owned").cow_owned();
+///     let borrowed: Cow<str> = "borrowed".cow_borrowed();
+///     assert!(matches!(owned, Cow::Owned(_)));
+///     assert!(matches!(borrowed, Cow::Borrowed(_)));
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub trait CowWrapExt: Sized {
+    /// Wraps the value in `Cow::Owned`.
+    #[inline(always)]
+    fn cow_owned<'a>(self) -> Cow<'a, Self::Target>
+    where
+        Self: core::ops::Deref,
+        Self::Target: ToOwned<Owned = Self>,
+    {
+        Cow::Owned(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> CowWrapExt for T {}
+
+/// Extension trait wrapping references into `Cow::Borrowed`.
+#[cfg(feature = "std")]
+pub trait CowBorrowedExt<'a, B: ?Sized + ToOwned> {
+    /// Wraps the reference in `Cow::Borrowed`.
+    fn cow_borrowed(self) -> Cow<'a, B>;
+}
+
+#[cfg(feature = "std")]
+impl<'a, B: ?Sized + ToOwned> CowBorrowedExt<'a, B> for &'a B {
+    #[inline(always)]
+    fn cow_borrowed(self) -> Cow<'a, B> {
+        Cow::Borrowed(self)
+    }
+}
+
+// ============================================================================
+// BoxConvertExt Trait - Box to Rc/Arc Conversion
+// ============================================================================
+
+/// Extension trait converting a `Box<T>` into `Rc<T>` or `Arc<T>`.
+///
+/// These delegate to the standard `From<Box<T>>` impls and work for unsized
+/// `T` such as slices, `str` and trait objects. Note that the box's allocation
+/// is *not* reused: `Rc` and `Arc` keep their reference counts in front of the
+/// value, so the value is moved (a bitwise copy, never a `Clone`) into a new
+/// allocation and the box is freed.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::BoxConvertExt;
+///     use std::sync::Arc;
+///
+///     let boxed: Box<[u32]> = vec![1, 2, 3].into_boxed_slice();
+///     let shared: Arc<[u32]> = boxed.into_arc();
+///     assert_eq!(&*shared, [1, 2, 3]);
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub trait BoxConvertExt<T: ?Sized> {
+    /// Converts into an `Rc<T>`.
+    fn into_rc(self) -> Rc<T>;
+
+    /// Converts into an `Arc<T>`.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    fn into_arc(self) -> Arc<T>;
+}
+
+#[cfg(feature = "std")]
+impl<T: ?Sized> BoxConvertExt<T> for Box<T> {
+    #[inline(always)]
+    fn into_rc(self) -> Rc<T> {
+        Rc::from(self)
+    }
+
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn into_arc(self) -> Arc<T> {
+        Arc::from(self)
+    }
+}
+
+// ============================================================================
+// Type-Erased Arc Downcasting
+// ============================================================================
+
+/// Downcasts a type-erased `Arc` back to its concrete type.
+///
+/// On a type mismatch the original `Arc` is handed back in `Err`, so it can
+/// be tried against another type or put back where it came from. The
+/// allocation is shared either way; nothing is cloned.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::{downcast_arc, WrapExt};
+///     use std::any::{Any, TypeId};
+///     use std::collections::HashMap;
+///     use std::sync::Arc;
+///
+///     let mut resources: HashMap<TypeId, Arc<dyn Any + Send + Sync>> = HashMap::new();
+///     resources.insert(TypeId::of::<u32>(), 7u32.arc_any());
+///
+///     let port = downcast_arc::<u32>(Arc::clone(&resources[&TypeId::of::<u32>()]));
+///     assert_eq!(*port.unwrap(), 7);
+/// }
+/// ```
+#[cfg(all(feature = "std", feature = "sync"))]
+#[inline]
+pub fn downcast_arc<T>(
+    any: Arc<dyn core::any::Any + Send + Sync>,
+) -> Result<Arc<T>, Arc<dyn core::any::Any + Send + Sync>>
+where
+    T: Send + Sync + 'static,
+{
+    any.downcast::<T>()
+}
+
+// ============================================================================
+// Trait Object Coercion Macros
+// ============================================================================
+
+/// Wraps a value in a `Box<dyn Trait>`, performing the unsized coercion.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     let shown = autowrap::box_dyn!(dyn std::fmt::Display, 42u8);
+///     assert_eq!(shown.to_string(), "42");
+/// }
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! box_dyn {
+    ($target:ty, $value:expr $(,)?) => {{
+        let wrapped: $crate::__private::Box<$target> = $crate::__private::Box::new($value);
+        wrapped
+    }};
+}
+
+/// Wraps a value in an `Rc<dyn Trait>`, performing the unsized coercion.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     let shown = autowrap::rc_dyn!(dyn std::fmt::Debug, "text");
+///     assert_eq!(format!("{:?}", shown), "\"text\"");
+/// }
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! rc_dyn {
+    ($target:ty, $value:expr $(,)?) => {{
+        let wrapped: $crate::__private::Rc<$target> = $crate::__private::Rc::new($value);
+        wrapped
+    }};
+}
+
+/// Wraps a value in an `Arc<dyn Trait>`, performing the unsized coercion.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     let plugins = vec![
+///         autowrap::arc_dyn!(dyn Fn() -> i32 + Send + Sync, || 1),
+///         autowrap::arc_dyn!(dyn Fn() -> i32 + Send + Sync, || 2),
+///     ];
+///     assert_eq!(plugins.iter().map(|f| f()).sum::<i32>(), 3);
+/// }
+/// ```
+#[cfg(all(feature = "std", feature = "sync"))]
+#[macro_export]
+macro_rules! arc_dyn {
+    ($target:ty, $value:expr $(,)?) => {{
+        let wrapped: $crate::__private::Arc<$target> = $crate::__private::Arc::new($value);
+        wrapped
+    }};
+}
+
+/// Wraps a value in a `Pin<Arc<dyn Trait + Send + Sync>>`.
+///
+/// Intended for pinned components shared across threads, such as
+/// self-referential state machines.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use std::fmt::Debug;
+///
+///     let pinned = autowrap::arc_pin_dyn!(Debug, 5u32);
+///     assert_eq!(format!("{:?}", pinned), "5");
+/// }
+/// ```
+#[cfg(all(feature = "std", feature = "sync"))]
+#[macro_export]
+macro_rules! arc_pin_dyn {
+    ($trait:path, $value:expr $(,)?) => {{
+        let pinned: $crate::__private::Pin<$crate::__private::Arc<dyn $trait + Send + Sync>> =
+            $crate::__private::Arc::pin($value);
+        pinned
+    }};
+}
+
+// ============================================================================
+// Default Constructors
+// ============================================================================
+
+/// Creates an `Arc<T>` holding `T::default()`.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     let buffer = autowrap::arc_default::<Vec<u8>>();
+///     assert!(buffer.is_empty());
+/// }
+/// ```
+#[cfg(all(feature = "std", feature = "sync"))]
+#[inline(always)]
+pub fn arc_default<T: Default>() -> Arc<T> {
+    Arc::new(T::default())
+}
+
+/// Creates an `Arc<Mutex<T>>` holding `T::default()`.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     let buffer = autowrap::arc_mutex_default::<Vec<u8>>();
+///     buffer.lock().unwrap().push(1);
+/// }
+/// ```
+#[cfg(all(feature = "std", feature = "sync"))]
+#[inline(always)]
+pub fn arc_mutex_default<T: Default>() -> Arc<Mutex<T>> {
+    Arc::new(Mutex::new(T::default()))
+}
+
+/// Creates an `Rc<RefCell<T>>` holding `T::default()`.
+#[cfg(feature = "std")]
+#[inline(always)]
+pub fn rc_refcell_default<T: Default>() -> Rc<RefCell<T>> {
+    Rc::new(RefCell::new(T::default()))
+}
+
+/// Creates an empty `Rc<RefCell<Vec<T>>>` with room for at least `capacity`
+/// elements, so the first pushes don't reallocate.
+#[cfg(feature = "std")]
+#[inline(always)]
+pub fn rc_refcell_vec_with_capacity<T>(capacity: usize) -> Rc<RefCell<Vec<T>>> {
+    Rc::new(RefCell::new(Vec::with_capacity(capacity)))
+}
+
+/// Creates an empty `Arc<Mutex<Vec<T>>>` with room for at least `capacity`
+/// elements.
+#[cfg(all(feature = "std", feature = "sync"))]
+#[inline(always)]
+pub fn arc_mutex_vec_with_capacity<T>(capacity: usize) -> Arc<Mutex<Vec<T>>> {
+    Arc::new(Mutex::new(Vec::with_capacity(capacity)))
+}
+
+// ============================================================================
+// Cyclic Constructors
+// ============================================================================
+
+/// Builds an `Arc<T>` whose contents may hold a `Weak` reference to itself.
+///
+/// Thin wrapper over `Arc::new_cyclic`. The `Weak` passed to `init` cannot be
+/// upgraded until construction finishes.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use std::sync::Weak;
+///
+///     struct Node {
+///         me: Weak<Node>,
+///     }
+///
+///     let node = autowrap::new_cyclic_wrap(|me: &Weak<Node>| Node { me: me.clone() });
+///     assert!(std::sync::Arc::ptr_eq(&node.me.upgrade().unwrap(), &node));
+/// }
+/// ```
+#[cfg(all(feature = "std", feature = "sync"))]
+#[inline(always)]
+pub fn new_cyclic_wrap<T, F>(init: F) -> Arc<T>
+where
+    F: FnOnce(&std::sync::Weak<T>) -> T,
+{
+    Arc::new_cyclic(init)
+}
+
+/// Builds an `Rc<T>` whose contents may hold a `Weak` reference to itself.
+///
+/// Thin wrapper over `Rc::new_cyclic`. The `Weak` passed to `init` cannot be
+/// upgraded until construction finishes.
+#[cfg(feature = "std")]
+#[inline(always)]
+pub fn rc_new_cyclic<T, F>(init: F) -> Rc<T>
+where
+    F: FnOnce(&std::rc::Weak<T>) -> T,
+{
+    Rc::new_cyclic(init)
+}
+
+// ============================================================================
+// MutexExt / RwLockExt Traits - Poison Recovery
+// ============================================================================
+
+/// Poison-tolerant locking for `Mutex<T>` (and `Arc<Mutex<T>>` via deref).
+///
+/// A mutex is poisoned when a thread panics while holding its guard. For
+/// state that stays usable after such a panic, these accessors hand back the
+/// guard anyway instead of returning an error. The data may be in whatever
+/// state the panicking thread left it.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::{MutexExt, WrapExt};
+///
+///     let counter = 0u32.arc_mutex();
+///     *counter.lock_ignore_poison() += 1;
+///     assert_eq!(*counter.lock_ignore_poison(), 1);
+/// }
+/// ```
+#[cfg(all(feature = "std", feature = "sync"))]
+pub trait MutexExt<T: ?Sized> {
+    /// Locks the mutex, returning the guard even if the mutex is poisoned.
+    fn lock_ignore_poison(&self) -> MutexGuard<'_, T>;
+
+    /// Locks the mutex like [`lock_ignore_poison`](MutexExt::lock_ignore_poison)
+    /// and clears the poison flag, so later `lock()` calls succeed again.
+    fn lock_clear_poison(&self) -> MutexGuard<'_, T>;
+
+    /// Locks the mutex, spinning on `try_lock` for up to `spins` attempts
+    /// before falling back to a blocking `lock`. Poisoning is ignored.
+    ///
+    /// Each failed attempt waits twice as many `spin_loop` hints as the last,
+    /// capped at 64, so a briefly held lock is usually taken without the
+    /// thread ever sleeping in the kernel. Only worth it for critical
+    /// sections of a few instructions; `spins == 0` is a plain
+    /// [`lock_ignore_poison`](MutexExt::lock_ignore_poison).
+    fn lock_spin(&self, spins: u32) -> MutexGuard<'_, T>;
+
+    /// Returns a clone of the protected value.
+    ///
+    /// The lock is held only for the duration of the clone and is released
+    /// before returning. Poisoning is ignored.
+    fn snapshot(&self) -> T
+    where
+        T: Clone;
+}
+
+#[cfg(all(feature = "std", feature = "sync"))]
+impl<T: ?Sized> MutexExt<T> for Mutex<T> {
+    #[inline(always)]
+    fn lock_ignore_poison(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline]
+    fn lock_clear_poison(&self) -> MutexGuard<'_, T> {
+        let guard = self.lock_ignore_poison();
+        self.clear_poison();
+        guard
+    }
+
+    fn lock_spin(&self, spins: u32) -> MutexGuard<'_, T> {
+        let mut backoff = 1u32;
+        for _ in 0..spins {
+            match self.try_lock() {
+                Ok(guard) => return guard,
+                Err(TryLockError::Poisoned(poisoned)) => return poisoned.into_inner(),
+                Err(TryLockError::WouldBlock) => {
+                    for _ in 0..backoff {
+                        core::hint::spin_loop();
+                    }
+                    backoff = (backoff * 2).min(64);
+                }
+            }
+        }
+        self.lock_ignore_poison()
+    }
+
+    #[inline]
+    fn snapshot(&self) -> T
+    where
+        T: Clone,
+    {
+        self.lock_ignore_poison().clone()
+    }
+}
+
+/// Poison-tolerant locking for `RwLock<T>` (and `Arc<RwLock<T>>` via deref).
+///
+/// See [`MutexExt`] for the recovery semantics.
+#[cfg(all(feature = "std", feature = "sync"))]
+pub trait RwLockExt<T: ?Sized> {
+    /// Acquires a read guard, even if the lock is poisoned.
+    fn read_ignore_poison(&self) -> RwLockReadGuard<'_, T>;
+
+    /// Acquires a write guard, even if the lock is poisoned.
+    fn write_ignore_poison(&self) -> RwLockWriteGuard<'_, T>;
+
+    /// Acquires a write guard and clears the poison flag.
+    fn write_clear_poison(&self) -> RwLockWriteGuard<'_, T>;
+
+    /// Returns a clone of the protected value, taken under a read lock.
+    ///
+    /// The read guard is released before returning. Poisoning is ignored.
+    fn snapshot(&self) -> T
+    where
+        T: Clone;
+}
+
+#[cfg(all(feature = "std", feature = "sync"))]
+impl<T: ?Sized> RwLockExt<T> for RwLock<T> {
+    #[inline(always)]
+    fn read_ignore_poison(&self) -> RwLockReadGuard<'_, T> {
+        self.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline(always)]
+    fn write_ignore_poison(&self) -> RwLockWriteGuard<'_, T> {
+        self.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline]
+    fn write_clear_poison(&self) -> RwLockWriteGuard<'_, T> {
+        let guard = self.write_ignore_poison();
+        self.clear_poison();
+        guard
+    }
+
+    #[inline]
+    fn snapshot(&self) -> T
+    where
+        T: Clone,
+    {
+        self.read_ignore_poison().clone()
+    }
+}
+
+// ============================================================================
+// TryLockExt / TryRwLockExt Traits - Non-Blocking Locking
+// ============================================================================
+
+/// Non-blocking locking for `Mutex<T>` (and `Arc<Mutex<T>>` via deref) that
+/// returns `Option`.
+///
+/// For paths that would rather skip work than wait on a contended lock.
+/// `None` means only "held elsewhere right now"; a poisoned mutex still
+/// hands out its guard, as with [`MutexExt::lock_ignore_poison`].
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::{TryLockExt, WrapExt};
+///
+///     let stats = 0u32.arc_mutex();
+///     let held = stats.lock().unwrap();
+///     assert!(stats.try_lock_opt().is_none());
+///     drop(held);
+///     *stats.try_lock_opt().unwrap() += 1;
+/// }
+/// ```
+#[cfg(all(feature = "std", feature = "sync"))]
+pub trait TryLockExt<T: ?Sized> {
+    /// Locks the mutex if it is free, ignoring poisoning.
+    fn try_lock_opt(&self) -> Option<MutexGuard<'_, T>>;
+}
+
+#[cfg(all(feature = "std", feature = "sync"))]
+impl<T: ?Sized> TryLockExt<T> for Mutex<T> {
+    #[inline]
+    fn try_lock_opt(&self) -> Option<MutexGuard<'_, T>> {
+        match self.try_lock() {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+}
+
+/// Non-blocking locking for `RwLock<T>` (and `Arc<RwLock<T>>` via deref)
+/// that returns `Option`.
+///
+/// See [`TryLockExt`] for the semantics.
+#[cfg(all(feature = "std", feature = "sync"))]
+pub trait TryRwLockExt<T: ?Sized> {
+    /// Acquires a read guard if no writer holds the lock, ignoring poisoning.
+    fn try_read_opt(&self) -> Option<RwLockReadGuard<'_, T>>;
+
+    /// Acquires a write guard if the lock is free, ignoring poisoning.
+    fn try_write_opt(&self) -> Option<RwLockWriteGuard<'_, T>>;
+}
+
+#[cfg(all(feature = "std", feature = "sync"))]
+impl<T: ?Sized> TryRwLockExt<T> for RwLock<T> {
+    #[inline]
+    fn try_read_opt(&self) -> Option<RwLockReadGuard<'_, T>> {
+        match self.try_read() {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+
+    #[inline]
+    fn try_write_opt(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        match self.try_write() {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+}
+
+// ============================================================================
+// UpgradableReadExt Trait - parking_lot Upgradable Reads
+// ============================================================================
+
+/// Extension trait for read-then-maybe-write access to a
+/// `parking_lot::RwLock<T>`.
+///
+/// An upgradable read coexists with plain readers but excludes writers and
+/// other upgradable readers, so after deciding a write is needed the guard
+/// can be upgraded without releasing the lock in between. Nothing can change
+/// the value between the check and the write.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "parking_lot")]
+/// {
+///     use autowrap::{UpgradableReadExt, WrapExt};
+///     use parking_lot::RwLockUpgradableReadGuard;
+///
+///     let cache = Vec::<u32>::new().arc_pl_rwlock();
+///     cache.with_upgradable_read(|guard| {
+///         if guard.is_empty() {
+///             let mut write = RwLockUpgradableReadGuard::upgrade(guard);
+///             write.push(1);
+///         }
+///     });
+///     assert_eq!(*cache.read(), [1]);
+/// }
+/// ```
+#[cfg(feature = "parking_lot")]
+pub trait UpgradableReadExt<T: ?Sized> {
+    /// Runs `f` with an upgradable read guard.
+    ///
+    /// Inside `f`, call `RwLockUpgradableReadGuard::upgrade` to turn the
+    /// guard into a write guard atomically. Whatever guard `f` ends up with
+    /// is released when it returns.
+    fn with_upgradable_read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(parking_lot::RwLockUpgradableReadGuard<'_, T>) -> R;
+}
+
+#[cfg(feature = "parking_lot")]
+impl<T: ?Sized> UpgradableReadExt<T> for parking_lot::RwLock<T> {
+    #[inline]
+    fn with_upgradable_read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(parking_lot::RwLockUpgradableReadGuard<'_, T>) -> R,
+    {
+        f(self.upgradable_read())
+    }
+}
+
+// ============================================================================
+// CellExt / RefCellExt Traits - Cell Helpers
+// ============================================================================
+
+/// Extension trait adding helpers to `Cell<T>`.
+///
+/// # Example
+///
+/// ```
+/// use autowrap::{CellExt, WrapExt};
+///
+/// let a = 1u8.cell();
+/// let b = 2u8.cell();
+/// a.swap_with(&b);
+/// assert_eq!((a.get(), b.get()), (2, 1));
+/// ```
+pub trait CellExt<T> {
+    /// Swaps the contents of the two cells.
+    ///
+    /// Swapping a cell with itself is a no-op.
+    fn swap_with(&self, other: &Cell<T>);
+}
+
+impl<T> CellExt<T> for Cell<T> {
+    #[inline(always)]
+    fn swap_with(&self, other: &Cell<T>) {
+        self.swap(other)
+    }
+}
+
+/// Extension trait for moving non-`Copy` values in and out of a
+/// `Cell<Option<T>>`.
+///
+/// The names differ from `Cell::take`/`Cell::set` because those inherent
+/// methods would shadow trait methods of the same name; `Cell::set` also
+/// takes an `Option<T>` rather than a `T`.
+///
+/// # Example
+///
+/// ```
+/// use autowrap::{CellOptionExt, WrapExt};
+///
+/// let slot = String::from("first").cell_option();
+/// assert_eq!(slot.take_value().as_deref(), Some("first"));
+/// assert_eq!(slot.take_value(), None);
+///
+/// slot.put(String::from("second"));
+/// assert_eq!(slot.replace_value(String::from("third")).as_deref(), Some("second"));
+/// ```
+pub trait CellOptionExt<T> {
+    /// Moves the value out, leaving `None`.
+    fn take_value(&self) -> Option<T>;
+
+    /// Stores `value`, dropping any previous one.
+    fn put(&self, value: T);
+
+    /// Stores `value` and returns the previous one, if any.
+    fn replace_value(&self, value: T) -> Option<T>;
+}
+
+impl<T> CellOptionExt<T> for Cell<Option<T>> {
+    #[inline(always)]
+    fn take_value(&self) -> Option<T> {
+        self.take()
+    }
+
+    #[inline(always)]
+    fn put(&self, value: T) {
+        self.set(Some(value))
+    }
+
+    #[inline(always)]
+    fn replace_value(&self, value: T) -> Option<T> {
+        self.replace(Some(value))
+    }
+}
+
+/// Extension trait adding helpers to `RefCell<T>`.
+#[cfg(feature = "std")]
+pub trait RefCellExt<T> {
+    /// Swaps the contents of the two cells.
+    ///
+    /// Unlike `RefCell::swap`, swapping a cell with itself is a no-op rather
+    /// than a double-borrow panic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either cell is currently borrowed.
+    fn swap_with(&self, other: &RefCell<T>);
+
+    /// Replaces the contents with the result of `f`, returning the old value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is currently borrowed.
+    fn replace_with_fn<F>(&self, f: F) -> T
+    where
+        F: FnOnce(&mut T) -> T;
+
+    /// Runs `f` with a shared borrow, or returns the borrow error if the cell
+    /// is mutably borrowed.
+    fn try_with<R, F>(&self, f: F) -> Result<R, BorrowError>
+    where
+        F: FnOnce(&T) -> R;
+
+    /// Runs `f` with a mutable borrow, or returns the borrow error if the cell
+    /// is borrowed.
+    fn try_with_mut<R, F>(&self, f: F) -> Result<R, BorrowMutError>
+    where
+        F: FnOnce(&mut T) -> R;
+
+    /// Runs `f` with a mutable borrow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is borrowed. Unlike `borrow_mut`, the message names
+    /// the caller's source location rather than std internals.
+    #[track_caller]
+    fn with_mut<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R;
+}
+
+#[cfg(feature = "std")]
+impl<T> RefCellExt<T> for RefCell<T> {
+    #[inline]
+    fn swap_with(&self, other: &RefCell<T>) {
+        if !core::ptr::eq(self, other) {
+            self.swap(other)
+        }
+    }
+
+    #[inline(always)]
+    fn replace_with_fn<F>(&self, f: F) -> T
+    where
+        F: FnOnce(&mut T) -> T,
+    {
+        self.replace_with(f)
+    }
+
+    #[inline]
+    fn try_with<R, F>(&self, f: F) -> Result<R, BorrowError>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.try_borrow().map(|value| f(&value))
+    }
+
+    #[inline]
+    fn try_with_mut<R, F>(&self, f: F) -> Result<R, BorrowMutError>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        self.try_borrow_mut().map(|mut value| f(&mut value))
+    }
+
+    #[inline]
+    #[track_caller]
+    fn with_mut<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        match self.try_borrow_mut() {
+            Ok(mut value) => f(&mut value),
+            Err(err) => panic!("{} at {}", err, core::panic::Location::caller()),
+        }
+    }
+}
+
+// ============================================================================
+// CellSliceExt / CellSliceWrapExt Traits - Element-Wise Cell Views
+// ============================================================================
+
+/// Extension trait viewing a `Cell` of a slice or array as a slice of cells.
+///
+/// This is `Cell::as_slice_of_cells`: a `Cell<[T]>` and a `[Cell<T>]` have
+/// the same layout, so each element can be read and written through `&self`
+/// without a `RefCell` around the whole slice.
+///
+/// # Example
+///
+/// ```
+/// use autowrap::{CellSliceExt, WrapExt};
+///
+/// let scores = [0u32; 3].cell();
+/// for (i, score) in scores.as_cell_slice().iter().enumerate() {
+///     score.set(i as u32 * 10);
+/// }
+/// assert_eq!(scores.get(), [0, 10, 20]);
+/// ```
+pub trait CellSliceExt<T> {
+    /// Returns the contents as a slice of cells.
+    fn as_cell_slice(&self) -> &[Cell<T>];
+}
+
+impl<T> CellSliceExt<T> for Cell<[T]> {
+    #[inline(always)]
+    fn as_cell_slice(&self) -> &[Cell<T>] {
+        self.as_slice_of_cells()
+    }
+}
+
+impl<T, const N: usize> CellSliceExt<T> for Cell<[T; N]> {
+    #[inline(always)]
+    fn as_cell_slice(&self) -> &[Cell<T>] {
+        let unsized_cell: &Cell<[T]> = self;
+        unsized_cell.as_slice_of_cells()
+    }
+}
+
+/// Extension trait moving an array, `Vec` or boxed slice into a
+/// `Box<Cell<[T]>>`.
+///
+/// The box owns the elements in place (the allocation is reused), and
+/// [`CellSliceExt::as_cell_slice`] on it gives the element-wise view. A
+/// `Cell<Box<[T]>>` would only allow swapping the whole box, hence the cell
+/// goes inside.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::{CellSliceExt, CellSliceWrapExt};
+///
+///     let flags = vec![false; 4].cell_slice();
+///     let view = flags.as_cell_slice();
+///     view[2].set(true);
+///     assert_eq!(view.iter().filter(|f| f.get()).count(), 1);
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub trait CellSliceWrapExt: Sized {
+    /// Moves the elements into a `Box<Cell<[T]>>`.
+    #[inline]
+    fn cell_slice<T>(self) -> Box<Cell<[T]>>
+    where
+        Self: Into<Box<[T]>>,
+    {
+        let slice: Box<[T]> = self.into();
+        // SAFETY: `Cell<U>` is `repr(transparent)` over `U` for unsized `U`
+        // too, so the pointer keeps its metadata and the allocation's layout.
+        unsafe { Box::from_raw(Box::into_raw(slice) as *mut Cell<[T]>) }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S> CellSliceWrapExt for S {}
+
+// ============================================================================
+// ScopedSetExt Trait - Restore-on-Drop Guards
+// ============================================================================
+
+/// Extension trait that sets a value for the length of a scope.
+///
+/// [`scoped_set`](ScopedSetExt::scoped_set) stores the new value right away
+/// and returns a guard that writes the previous value back when it drops,
+/// including while unwinding from a panic. That makes it a fit for
+/// reentrancy flags and test overrides, which must not stay set after a
+/// failure. Implemented for `Cell<T: Copy>` and, with the `sync` feature,
+/// for `AtomicBool` and the integer atomics (`AcqRel` swap on set, `Release`
+/// store on restore).
+///
+/// Guards restore in drop order, so nested guards on the same target unwind
+/// back to the outermost previous value.
+///
+/// # Example
+///
+/// ```
+/// use autowrap::{ScopedSetExt, WrapExt};
+///
+/// let in_callback = false.cell();
+/// {
+///     let _guard = in_callback.scoped_set(true);
+///     assert!(in_callback.get());
+/// }
+/// assert!(!in_callback.get());
+/// ```
+pub trait ScopedSetExt {
+    /// The type of the stored value.
+    type Value: Copy;
+
+    /// Stores `value`, returning a guard that restores the previous value
+    /// on drop.
+    fn scoped_set(&self, value: Self::Value) -> ScopedSetGuard<'_, Self>;
+
+    /// Writes `value` back; this is what [`ScopedSetGuard`] calls on drop.
+    fn scoped_restore(&self, value: Self::Value);
+}
+
+/// Guard returned by [`ScopedSetExt::scoped_set`].
+#[must_use = "the previous value is restored as soon as the guard is dropped"]
+pub struct ScopedSetGuard<'a, S: ScopedSetExt + ?Sized> {
+    target: &'a S,
+    previous: S::Value,
+}
+
+impl<'a, S: ScopedSetExt + ?Sized> ScopedSetGuard<'a, S> {
+    /// Builds a guard that will write `previous` back to `target`.
+    ///
+    /// For implementors of [`ScopedSetExt`]; `target` must already hold the
+    /// new value.
+    #[inline(always)]
+    pub fn new(target: &'a S, previous: S::Value) -> Self {
+        ScopedSetGuard { target, previous }
+    }
+
+    /// Returns the value that will be restored.
+    #[inline(always)]
+    pub fn previous(&self) -> S::Value {
+        self.previous
+    }
+}
+
+impl<S: ScopedSetExt + ?Sized> Drop for ScopedSetGuard<'_, S> {
+    #[inline]
+    fn drop(&mut self) {
+        self.target.scoped_restore(self.previous);
+    }
+}
+
+impl<S: ScopedSetExt + ?Sized> core::fmt::Debug for ScopedSetGuard<'_, S>
+where
+    S::Value: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ScopedSetGuard")
+            .field("previous", &self.previous)
+            .finish()
+    }
+}
+
+impl<T: Copy> ScopedSetExt for Cell<T> {
+    type Value = T;
+
+    #[inline(always)]
+    fn scoped_set(&self, value: T) -> ScopedSetGuard<'_, Self> {
+        ScopedSetGuard::new(self, self.replace(value))
+    }
+
+    #[inline(always)]
+    fn scoped_restore(&self, value: T) {
+        self.set(value)
+    }
+}
+
+macro_rules! impl_scoped_set {
+    ($($(#[$attr:meta])* $atomic:ty => $ty:ty),* $(,)?) => {
+        $(
+            #[cfg(feature = "sync")]
+            $(#[$attr])*
+            impl ScopedSetExt for $atomic {
+                type Value = $ty;
+
+                #[inline(always)]
+                fn scoped_set(&self, value: $ty) -> ScopedSetGuard<'_, Self> {
+                    ScopedSetGuard::new(self, self.swap(value, Ordering::AcqRel))
+                }
+
+                #[inline(always)]
+                fn scoped_restore(&self, value: $ty) {
+                    self.store(value, Ordering::Release)
+                }
+            }
+        )*
+    };
+}
+
+impl_scoped_set! {
+    core::sync::atomic::AtomicU8 => u8,
+    core::sync::atomic::AtomicU16 => u16,
+    AtomicU32 => u32,
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))] AtomicU64 => u64,
+    #[cfg(feature = "portable-atomic")] AtomicU128 => u128,
+    AtomicUsize => usize,
+    core::sync::atomic::AtomicI8 => i8,
+    core::sync::atomic::AtomicI16 => i16,
+    AtomicI32 => i32,
+    #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))] AtomicI64 => i64,
+    #[cfg(feature = "portable-atomic")] AtomicI128 => i128,
+    AtomicIsize => isize,
+    AtomicBool => bool,
+}
+
+// ============================================================================
+// WeakExt Trait - Weak Reference Helpers
+// ============================================================================
+
+/// Extension trait for `rc::Weak<T>` and `sync::Weak<T>`.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::WeakExt;
+///     use std::rc::Rc;
+///
+///     let parent = Rc::new("root");
+///     let weak = Rc::downgrade(&parent);
+///     assert!(!weak.is_dangling());
+///
+///     drop(parent);
+///     assert!(weak.is_dangling());
+///     assert_eq!(*weak.upgrade_or(|| Rc::new("orphan")), "orphan");
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub trait WeakExt {
+    /// The strong pointer type this weak reference upgrades to.
+    type Strong;
+
+    /// Upgrades the reference, or calls `default` if the value was dropped.
+    fn upgrade_or<F>(&self, default: F) -> Self::Strong
+    where
+        F: FnOnce() -> Self::Strong;
+
+    /// Returns `true` if no strong references remain, i.e. `strong_count() == 0`.
+    ///
+    /// A weak reference created by `Weak::new` is always dangling.
+    fn is_dangling(&self) -> bool;
+}
+
+#[cfg(feature = "std")]
+impl<T: ?Sized> WeakExt for std::rc::Weak<T> {
+    type Strong = Rc<T>;
+
+    #[inline]
+    fn upgrade_or<F>(&self, default: F) -> Rc<T>
+    where
+        F: FnOnce() -> Rc<T>,
+    {
+        self.upgrade().unwrap_or_else(default)
+    }
+
+    #[inline(always)]
+    fn is_dangling(&self) -> bool {
+        self.strong_count() == 0
+    }
+}
+
+#[cfg(all(feature = "std", feature = "sync"))]
+impl<T: ?Sized> WeakExt for std::sync::Weak<T> {
+    type Strong = Arc<T>;
+
+    #[inline]
+    fn upgrade_or<F>(&self, default: F) -> Arc<T>
+    where
+        F: FnOnce() -> Arc<T>,
+    {
+        self.upgrade().unwrap_or_else(default)
+    }
+
+    #[inline(always)]
+    fn is_dangling(&self) -> bool {
+        self.strong_count() == 0
+    }
+}
+
+// ============================================================================
+// RcPtrExt / ArcPtrExt Traits - Pointer Identity
+// ============================================================================
+
+/// Extension trait comparing `Rc`s by identity.
+///
+/// `a.same_as(&b)` is `Rc::ptr_eq(&a, &b)`: `true` only for clones of the
+/// same allocation, never for separately built equal values. Wrap the `Rc`
+/// in [`ByAddress`] for identity-based hashing.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::{RcPtrExt, WrapExt};
+///     use std::rc::Rc;
+///
+///     let a = 1u8.rc();
+///     assert!(a.same_as(&Rc::clone(&a)));
+///     assert!(!a.same_as(&1u8.rc()));
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub trait RcPtrExt {
+    /// Returns `true` if both point to the same allocation.
+    fn same_as(&self, other: &Self) -> bool;
+}
+
+#[cfg(feature = "std")]
+impl<T: ?Sized> RcPtrExt for Rc<T> {
+    #[inline(always)]
+    fn same_as(&self, other: &Self) -> bool {
+        Rc::ptr_eq(self, other)
+    }
+}
+
+/// Extension trait comparing `Arc`s by identity.
+///
+/// See [`RcPtrExt`].
+#[cfg(all(feature = "std", feature = "sync"))]
+pub trait ArcPtrExt {
+    /// Returns `true` if both point to the same allocation.
+    fn same_as(&self, other: &Self) -> bool;
+}
+
+#[cfg(all(feature = "std", feature = "sync"))]
+impl<T: ?Sized> ArcPtrExt for Arc<T> {
+    #[inline(always)]
+    fn same_as(&self, other: &Self) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+}
+
+// ============================================================================
+// Thread-Local Cell Macro
+// ============================================================================
+
+/// Declares a thread-local `RefCell<T>` without the `thread_local!` and
+/// `RefCell::new` boilerplate.
+///
+/// `thread_local_cell!(NAME: T = expr);` expands to
+/// `thread_local! { static NAME: RefCell<T> = RefCell::new(expr); }`. Each
+/// thread lazily evaluates `expr` on first access and sees its own value.
+/// Access it through the std `LocalKey<RefCell<T>>` helpers such as
+/// `with_borrow`, `with_borrow_mut`, `set` and `take`.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     autowrap::thread_local_cell!(SCRATCH: Vec<u8> = Vec::new());
+///
+///     SCRATCH.with_borrow_mut(|buf| buf.push(1));
+///     assert_eq!(SCRATCH.with_borrow(|buf| buf.len()), 1);
+/// }
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! thread_local_cell {
+    ($(#[$attr:meta])* $vis:vis $name:ident : $t:ty = $init:expr $(;)?) => {
+        ::std::thread_local! {
+            $(#[$attr])* $vis static $name: ::std::cell::RefCell<$t> = ::std::cell::RefCell::new($init);
+        }
+    };
+}
+
+// ============================================================================
+// Lazy Global Macro
+// ============================================================================
+
+/// Declares a lazily initialized global `Arc<T>`.
+///
+/// `static_wrap!(NAME: Arc<T> = expr);` expands to a function `NAME()`
+/// returning `&'static Arc<T>`, backed by a `static OnceLock<Arc<T>>` inside
+/// it. The first call evaluates `expr`; concurrent first calls block until
+/// that one finishes, so `expr` runs exactly once per program.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use std::collections::HashMap;
+///     use std::sync::{Arc, RwLock};
+///
+///     autowrap::static_wrap!(REGISTRY: Arc<RwLock<HashMap<&'static str, u32>>> = Default::default());
+///
+///     REGISTRY().write().unwrap().insert("answer", 42);
+///     assert_eq!(REGISTRY().read().unwrap()["answer"], 42);
+///     assert!(Arc::ptr_eq(REGISTRY(), REGISTRY()));
+/// }
+/// ```
+#[cfg(all(feature = "std", feature = "sync"))]
+#[macro_export]
+macro_rules! static_wrap {
+    ($(#[$attr:meta])* $vis:vis $name:ident : Arc<$t:ty> = $init:expr $(;)?) => {
+        $(#[$attr])*
+        #[allow(non_snake_case)]
+        $vis fn $name() -> &'static $crate::__private::Arc<$t> {
+            static CELL: $crate::__private::OnceLock<$crate::__private::Arc<$t>> =
+                $crate::__private::OnceLock::new();
+            CELL.get_or_init(|| $crate::__private::Arc::new($init))
+        }
+    };
+}
+
+// ============================================================================
+// OnceCellExt Trait - Single-Threaded Lazy Initialization
+// ============================================================================
+
+/// Creates an empty `OnceCell<T>`.
+///
+/// The counterpart of [`WrapExt::once_cell`] for when no value exists yet.
+#[inline(always)]
+pub const fn once_cell_empty<T>() -> OnceCell<T> {
+    OnceCell::new()
+}
+
+/// Extension trait adding helpers to `OnceCell<T>`.
+///
+/// # Example
+///
+/// ```
+/// use autowrap::{once_cell_empty, OnceCellExt};
+///
+/// let cell = once_cell_empty::<u32>();
+/// assert_eq!(cell.get(), None);
+/// assert_eq!(*cell.get_or_wrap(|| 1), 1);
+/// assert_eq!(*cell.get_or_wrap(|| 2), 1);
+/// ```
+pub trait OnceCellExt<T> {
+    /// Returns the value, initializing it with `f` if the cell is empty.
+    ///
+    /// Once set, later initializers are ignored and never run.
+    fn get_or_wrap<F>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> T;
+}
+
+impl<T> OnceCellExt<T> for OnceCell<T> {
+    #[inline(always)]
+    fn get_or_wrap<F>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        self.get_or_init(f)
+    }
+}
+
+// ============================================================================
+// OnceLockExt Trait - Thread-Safe Lazy Initialization
+// ============================================================================
+
+/// Extension trait adding initialization helpers to `OnceLock<T>`.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::OnceLockExt;
+///     use std::sync::OnceLock;
+///
+///     static NAMES: OnceLock<Vec<String>> = OnceLock::new();
+///     assert!(NAMES.get_or_default().is_empty());
+///
+///     let port: OnceLock<u16> = OnceLock::new();
+///     assert!(port.get_or_try_wrap(|| "http".parse::<u16>()).is_err());
+///     assert_eq!(port.get_or_try_wrap(|| "8080".parse::<u16>()), Ok(&8080));
+/// }
+/// ```
+#[cfg(all(feature = "std", feature = "sync"))]
+pub trait OnceLockExt<T> {
+    /// Returns the value, initializing it with `T::default()` if empty.
+    fn get_or_default(&self) -> &T
+    where
+        T: Default;
+
+    /// Returns the value, initializing it with `f` if empty and `f` succeeds.
+    ///
+    /// An error from `f` is returned and leaves the lock empty, so a later
+    /// call may retry. Unlike `get_or_init`, concurrent first callers are not
+    /// serialized: each may run its own `f`, and the first successful value
+    /// to be stored wins while the others are dropped. The name avoids the
+    /// still-unstable `OnceLock::get_or_try_init`.
+    fn get_or_try_wrap<F, E>(&self, f: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Result<T, E>;
+}
+
+#[cfg(all(feature = "std", feature = "sync"))]
+impl<T> OnceLockExt<T> for OnceLock<T> {
+    #[inline]
+    fn get_or_default(&self) -> &T
+    where
+        T: Default,
+    {
+        self.get_or_init(T::default)
+    }
+
+    #[inline]
+    fn get_or_try_wrap<F, E>(&self, f: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        if let Some(value) = self.get() {
+            return Ok(value);
+        }
+        let value = f()?;
+        Ok(self.get_or_init(|| value))
+    }
+}
+
+// ============================================================================
+// Async Lazy Initialization
+// ============================================================================
+
+/// Creates an empty `tokio::sync::OnceCell<T>` for lazily initialized async
+/// resources.
+///
+/// `get_or_init` takes a closure returning the initializer future. Callers
+/// racing on an empty cell wait for the first initializer rather than
+/// starting their own, so it runs once; if it's cancelled or panics, the next
+/// caller tries again.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "tokio")]
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let pool = autowrap::async_once_cell::<Vec<&str>>();
+/// let conns = pool.get_or_init(|| async { vec!["conn-1"] }).await;
+/// assert_eq!(conns.len(), 1);
+/// # });
+/// ```
+#[cfg(feature = "tokio")]
+#[inline(always)]
+pub fn async_once_cell<T>() -> tokio::sync::OnceCell<T> {
+    tokio::sync::OnceCell::new()
+}
+
+// ============================================================================
+// CollectionWrapExt Trait - Shared Collection Wrappers
+// ============================================================================
+
+/// Extension trait wrapping collections into the usual shared forms.
+///
+/// Anything convertible into the collection is accepted, so arrays and
+/// slices work as well as `Vec`/`HashMap` themselves.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::CollectionWrapExt;
+///
+///     let queue = [1, 2, 3].arc_mutex_vec();
+///     queue.lock().unwrap().push(4);
+///
+///     let index = [("a", 1)].arc_rwlock_hashmap();
+///     assert_eq!(index.read().unwrap()["a"], 1);
+/// }
+/// ```
+#[cfg(all(feature = "std", feature = "sync"))]
+pub trait CollectionWrapExt: Sized {
+    /// Wraps the value as an `Arc<Mutex<Vec<T>>>`.
+    #[inline(always)]
+    fn arc_mutex_vec<T>(self) -> Arc<Mutex<Vec<T>>>
+    where
+        Self: Into<Vec<T>>,
+    {
+        Arc::new(Mutex::new(self.into()))
+    }
+
+    /// Wraps the value as an `Arc<RwLock<HashMap<K, V, S>>>`.
+    #[inline(always)]
+    fn arc_rwlock_hashmap<K, V, S>(self) -> Arc<RwLock<std::collections::HashMap<K, V, S>>>
+    where
+        Self: Into<std::collections::HashMap<K, V, S>>,
+    {
+        Arc::new(RwLock::new(self.into()))
+    }
+}
+
+#[cfg(all(feature = "std", feature = "sync"))]
+impl<T> CollectionWrapExt for T {}
+
+// ============================================================================
+// IterCollectWrapExt Trait - Collecting Into Shared Slices
+// ============================================================================
+
+/// Extension trait collecting an iterator straight into `Rc<[T]>`/`Arc<[T]>`.
+///
+/// These use the standard `FromIterator` impls for `Rc<[T]>` and `Arc<[T]>`.
+/// When the iterator reports an exact length that the compiler can trust
+/// (ranges, slice and `Vec` iterators, and `map`/`rev`/`zip` over them) the
+/// slice is written into a single allocation. For other iterators, such as
+/// `filter` or `flat_map`, the length isn't known up front, so the items are
+/// gathered in a `Vec` first and moved into the shared allocation at the end:
+/// the same cost as `collect::<Vec<_>>().into()`, no worse.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::IterCollectWrapExt;
+///     use std::sync::Arc;
+///
+///     let squares: Arc<[u32]> = (1..=4).map(|n| n * n).collect_arc_slice();
+///     assert_eq!(&*squares, [1, 4, 9, 16]);
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub trait IterCollectWrapExt: Iterator + Sized {
+    /// Collects the items into an `Rc<[T]>`.
+    #[inline(always)]
+    fn collect_rc_slice(self) -> Rc<[Self::Item]> {
+        self.collect()
+    }
+
+    /// Collects the items into an `Arc<[T]>`.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline(always)]
+    fn collect_arc_slice(self) -> Arc<[Self::Item]> {
+        self.collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I: Iterator> IterCollectWrapExt for I {}
+
+// ============================================================================
+// VecWrapExt Trait - Per-Element Shared Wrappers
+// ============================================================================
+
+/// Extension trait consuming a `Vec<T>` and wrapping each element on its
+/// own.
+///
+/// Unlike an `Rc<[T]>`, every element gets its own allocation and refcount,
+/// so elements can be handed out and outlive each other independently. The
+/// original buffer can't be reused (an `Rc<T>` is not the size of a `T`), so
+/// this costs one new `Vec` plus one allocation per element.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::VecWrapExt;
+///     use std::sync::Arc;
+///
+///     let jobs = vec![String::from("a"), String::from("b")].into_arc_elements();
+///     let worker_copy = Arc::clone(&jobs[0]);
+///     assert_eq!(Arc::strong_count(&jobs[0]), 2);
+///     assert_eq!(Arc::strong_count(&jobs[1]), 1);
+///     assert_eq!(*worker_copy, "a");
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub trait VecWrapExt<T> {
+    /// Wraps each element in an `Rc<T>`.
+    fn into_rc_elements(self) -> Vec<Rc<T>>;
+
+    /// Wraps each element in an `Arc<T>`.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    fn into_arc_elements(self) -> Vec<Arc<T>>;
+
+    /// Moves the elements into a [`ConcurrentQueue`], first element at the
+    /// front, reusing the buffer.
+    #[cfg(all(feature = "std", feature = "sync"))]
+    fn concurrent_queue(self) -> ConcurrentQueue<T>;
+}
+
+#[cfg(feature = "std")]
+impl<T> VecWrapExt<T> for Vec<T> {
+    #[inline]
+    fn into_rc_elements(self) -> Vec<Rc<T>> {
+        self.into_iter().map(Rc::new).collect()
+    }
+
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline]
+    fn into_arc_elements(self) -> Vec<Arc<T>> {
+        self.into_iter().map(Arc::new).collect()
+    }
+
+    #[cfg(all(feature = "std", feature = "sync"))]
+    #[inline]
+    fn concurrent_queue(self) -> ConcurrentQueue<T> {
+        ConcurrentQueue::from(self)
+    }
+}
+
+// ============================================================================
+// MapWrapExt Trait - Arc-Valued Map Helpers
+// ============================================================================
+
+/// Extension trait for `HashMap<K, Arc<V>>` caches.
+///
+/// Both methods hand back an `Arc` sharing the entry's allocation, so the
+/// caller can keep the value without cloning it out of the map by hand.
+/// Implemented for `std::collections::HashMap` and, with the `hashbrown`
+/// feature, for `hashbrown::HashMap`, which also works in `no_std` builds.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::MapWrapExt;
+///     use std::collections::HashMap;
+///     use std::sync::Arc;
+///
+///     let mut cache = HashMap::new();
+///     let config = cache.insert_arc("config", String::from("v1"));
+///     assert_eq!(Arc::strong_count(&config), 2);
+///
+///     let again = cache.get_or_insert_arc("config", || unreachable!());
+///     assert!(Arc::ptr_eq(&config, &again));
+/// }
+/// ```
+#[cfg(any(all(feature = "std", feature = "sync"), feature = "hashbrown"))]
+pub trait MapWrapExt<K, V> {
+    /// Wraps `value` in an `Arc`, stores a clone under `key`, and returns the
+    /// `Arc`. Any previous value for `key` is replaced.
+    fn insert_arc(&mut self, key: K, value: V) -> alloc::sync::Arc<V>;
+
+    /// Returns the `Arc` stored under `key`, first inserting one built from
+    /// `f` if the key is absent.
+    fn get_or_insert_arc<F>(&mut self, key: K, f: F) -> alloc::sync::Arc<V>
+    where
+        F: FnOnce() -> V;
+}
+
+macro_rules! impl_map_wrap {
+    ($(#[$cfg:meta] $($map:ident)::+),* $(,)?) => {
+        $(
+            #[$cfg]
+            impl<K, V, S> MapWrapExt<K, V> for $($map)::+<K, alloc::sync::Arc<V>, S>
+            where
+                K: Eq + core::hash::Hash,
+                S: core::hash::BuildHasher,
+            {
+                #[inline]
+                fn insert_arc(&mut self, key: K, value: V) -> alloc::sync::Arc<V> {
+                    let arc = alloc::sync::Arc::new(value);
+                    self.insert(key, alloc::sync::Arc::clone(&arc));
+                    arc
+                }
+
+                #[inline]
+                fn get_or_insert_arc<F>(&mut self, key: K, f: F) -> alloc::sync::Arc<V>
+                where
+                    F: FnOnce() -> V,
+                {
+                    alloc::sync::Arc::clone(self.entry(key).or_insert_with(|| alloc::sync::Arc::new(f())))
+                }
+            }
+        )*
+    };
+}
+
+impl_map_wrap! {
+    #[cfg(all(feature = "std", feature = "sync"))] std::collections::HashMap,
+    #[cfg(feature = "hashbrown")] hashbrown::HashMap,
+}
+
+// ============================================================================
+// IntoShared / IntoLocal Traits - Thread-Safety Parameterized Wrapping
+// ============================================================================
+
+/// Converts a value into its thread-safe shared form, `Arc<Mutex<T>>`.
+///
+/// Together with [`IntoLocal`] this lets generic code ask for "a value that
+/// can be shared across threads" as a bound, and reach the value through
+/// [`with_shared`](IntoShared::with_shared) without naming the wrapper.
+/// Both traits have blanket impls covering every suitable type, so no other
+/// impls can be added.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::IntoShared;
+///     use std::thread;
+///
+///     fn bump_on_thread<T: IntoShared>(value: T, bump: fn(&mut T)) -> T::Shared {
+///         let shared = value.into_shared();
+///         thread::scope(|s| {
+///             s.spawn(|| T::with_shared(&shared, bump));
+///         });
+///         shared
+///     }
+///
+///     let count = bump_on_thread(1i32, |n| *n += 1);
+///     assert_eq!(i32::with_shared(&count, |n| *n), 2);
+/// }
+/// ```
+#[cfg(all(feature = "std", feature = "sync"))]
+pub trait IntoShared: Sized + Send {
+    /// The shared wrapper; clones refer to the same value.
+    type Shared: Clone + Send + Sync;
+
+    /// Wraps the value in its shared form.
+    fn into_shared(self) -> Self::Shared;
+
+    /// Runs `f` with exclusive access to the shared value.
+    ///
+    /// A poisoned lock is recovered rather than propagated.
+    fn with_shared<R>(shared: &Self::Shared, f: impl FnOnce(&mut Self) -> R) -> R;
+}
+
+#[cfg(all(feature = "std", feature = "sync"))]
+impl<T: Send> IntoShared for T {
+    type Shared = Arc<Mutex<T>>;
+
+    #[inline(always)]
+    fn into_shared(self) -> Arc<Mutex<T>> {
+        Arc::new(Mutex::new(self))
+    }
+
+    #[inline]
+    fn with_shared<R>(shared: &Arc<Mutex<T>>, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut shared.lock_ignore_poison())
+    }
+}
+
+/// Converts a value into its single-threaded shared form, `Rc<RefCell<T>>`.
+///
+/// The local counterpart of [`IntoShared`].
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::IntoLocal;
+///
+///     let names = vec!["a"].into_local();
+///     let alias = names.clone();
+///     Vec::with_local(&alias, |v| v.push("b"));
+///     assert_eq!(Vec::with_local(&names, |v| v.len()), 2);
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub trait IntoLocal: Sized {
+    /// The shared wrapper; clones refer to the same value.
+    type Local: Clone;
+
+    /// Wraps the value in its local shared form.
+    fn into_local(self) -> Self::Local;
+
+    /// Runs `f` with exclusive access to the shared value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is already borrowed, e.g. from inside another
+    /// `with_local` on the same value.
+    fn with_local<R>(local: &Self::Local, f: impl FnOnce(&mut Self) -> R) -> R;
+}
+
+#[cfg(feature = "std")]
+impl<T> IntoLocal for T {
+    type Local = Rc<RefCell<T>>;
+
+    #[inline(always)]
+    fn into_local(self) -> Rc<RefCell<T>> {
+        Rc::new(RefCell::new(self))
+    }
+
+    #[inline]
+    fn with_local<R>(local: &Rc<RefCell<T>>, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut local.borrow_mut())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "spin")]
+    #[test]
+    fn test_spin_mutex_no_std() {
+        let counter = 0u32.spin_mutex();
+        *counter.lock() += 2;
+        {
+            let guard = counter.lock();
+            assert!(counter.try_lock().is_none());
+            assert_eq!(*guard, 2);
+        }
+        assert_eq!(counter.into_inner(), 2);
+    }
+
+    #[cfg(feature = "spin")]
+    #[test]
+    fn test_spin_rwlock_no_std() {
+        let table = [1u8, 2, 3].spin_rwlock();
+        {
+            let (a, b) = (table.read(), table.read());
+            assert_eq!(a[0] + b[2], 4);
+            assert!(table.try_write().is_none());
+        }
+        table.write()[1] = 20;
+        assert_eq!(table.read()[1], 20);
+    }
+
+    #[cfg(feature = "hashbrown")]
+    #[test]
+    fn test_hashbrown_insert_arc_no_std() {
+        use alloc::sync::Arc;
+
+        let mut cache = hashbrown::HashMap::new();
+        let first = cache.insert_arc(1u32, [1u8, 2]);
+        assert_eq!(Arc::strong_count(&first), 2);
+
+        let again = cache.get_or_insert_arc(1, || unreachable!());
+        assert!(Arc::ptr_eq(&first, &again));
+
+        let built = cache.get_or_insert_arc(2, || [3, 4]);
+        assert_eq!((cache.len(), *built), (2, [3, 4]));
+    }
+
+    #[test]
+    fn test_cell_array_as_cell_slice() {
+        let grid = [1u8, 2, 3, 4].cell();
+        let cells = grid.as_cell_slice();
+        cells[0].set(10);
+        cells[3].swap(&cells[1]);
+        assert_eq!(grid.get(), [10, 4, 3, 2]);
+
+        let unsized_grid: &Cell<[u8]> = &grid;
+        unsized_grid.as_cell_slice()[2].set(30);
+        assert_eq!(grid.get(), [10, 4, 30, 2]);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_atomic_wrap_no_std() {
+        let hits = 0u32.atomic_u32();
+        assert_eq!(hits.fetch_add(2, Ordering::Relaxed), 0);
+        assert_eq!(hits.fetch_add_acq_rel(3), 2);
+        assert_eq!(hits.into_value(), 5);
+
+        let flag = false.atomic_bool();
+        {
+            let _busy = flag.scoped_set(true);
+            assert!(flag.load(Ordering::Acquire));
+        }
+        assert!(!flag.load(Ordering::Relaxed));
+    }
+
+    #[cfg(feature = "portable-atomic")]
+    #[test]
+    fn test_portable_atomic_wide_fetch_add() {
+        let total: portable_atomic::AtomicU64 = 40u32.atomic_u64();
+        assert_eq!(total.fetch_add(2, Ordering::AcqRel), 40);
+        assert_eq!(total.into_value(), 42);
+
+        let wide: portable_atomic::AtomicU128 = u64::MAX.atomic_u128();
+        assert_eq!(wide.fetch_add(1, Ordering::Relaxed), u64::MAX as u128);
+        assert_eq!(wide.load(Ordering::Relaxed), 1 << 64);
+        assert_eq!((-1i64).atomic_i128().fetch_sub(1, Ordering::Relaxed), -1);
+    }
+
+    #[test]
+    fn test_cell_wrap() {
+        let c = 10u32.cell();
+        assert_eq!(c.get(), 10);
+        c.set(20);
+        assert_eq!(c.get(), 20);
+    }
+
+    #[test]
+    fn test_non_zero_wrap() {
+        assert!(0u32.non_zero().is_none());
+        assert_eq!(5u32.non_zero().map(|n| n.get()), Some(5));
+        assert_eq!((-3i8).non_zero().map(|n| n.get()), Some(-3));
+        assert!(0usize.non_zero().is_none());
+
+        // SAFETY: 7 is not zero.
+        let n = unsafe { 7u64.non_zero_unchecked() };
+        assert_eq!(n.get(), 7);
+    }
+
+    #[test]
+    fn test_manually_drop_wrap() {
+        let wrapped = 42u32.manually_drop();
+        assert_eq!(*wrapped, 42);
+        assert_eq!(ManuallyDrop::into_inner(wrapped), 42);
+    }
+
+    #[test]
+    fn test_maybe_uninit_wrap() {
+        let wrapped = 7u64.maybe_uninit();
+        // SAFETY: `maybe_uninit` always fully initializes the value.
+        assert_eq!(unsafe { wrapped.assume_init() }, 7);
+    }
+
+    #[test]
+    fn test_reversed_wrap() {
+        assert!(1u32.reversed() > 2u32.reversed());
+        assert_eq!(5u8.reversed().0, 5);
+    }
+
+    #[test]
+    fn test_option_result_wrap() {
+        assert_eq!(5.some(), Some(5));
+        assert_eq!(5u8.ok::<()>(), Ok(5));
+        assert_eq!("bad".err::<i32>(), Err("bad"));
+
+        let nested: Result<u8, ()> = Ok(1);
+        assert_eq!(WrapExt::ok::<()>(nested), Ok(Ok(1)));
+    }
+
+    #[test]
+    fn test_cell_swap_with() {
+        let a = 1u32.cell();
+        let b = 2u32.cell();
+        a.swap_with(&b);
+        assert_eq!((a.get(), b.get()), (2, 1));
+
+        a.swap_with(&a);
+        assert_eq!(a.get(), 2);
+    }
+
+    #[test]
+    fn test_once_cell_wrap() {
+        let filled = 3u32.once_cell();
+        assert_eq!(filled.get(), Some(&3));
+        assert_eq!(*filled.get_or_wrap(|| 4), 3);
+    }
+
+    #[test]
+    fn test_once_cell_empty() {
+        let cell = once_cell_empty::<u32>();
+        assert_eq!(cell.get(), None);
+
+        let mut second_ran = false;
+        assert_eq!(*cell.get_or_wrap(|| 10), 10);
+        assert_eq!(
+            *cell.get_or_wrap(|| {
+                second_ran = true;
+                20
+            }),
+            10
+        );
+        assert!(!second_ran);
+        assert_eq!(cell.get(), Some(&10));
+    }
+
+    #[test]
+    fn test_const_constructors() {
+        const fn start() -> Cell<u32> {
+            const_cell(5)
+        }
+        const fn raw() -> UnsafeCell<u8> {
+            const_unsafe_cell(9)
+        }
+
+        let counter = start();
+        let raw = raw();
+
+        counter.set(counter.get() + 1);
+        assert_eq!(counter.get(), 6);
+        assert_eq!(raw.into_inner(), 9);
+    }
+
+    #[test]
+    fn test_unsafe_cell_wrap() {
+        let cell = 11u32.unsafe_cell();
+        let write: *mut u32 = cell.get();
+        let read: *const u32 = cell.get();
+
+        // SAFETY: no references to the contents exist while we go through
+        // the raw pointers, and the cell outlives both accesses.
+        unsafe {
+            *write += 1;
+            assert_eq!(*read, 12);
+        }
+        assert_eq!(cell.into_inner(), 12);
+    }
+
+    #[test]
+    fn test_cell_pair_wrap() {
+        let (count, done) = (0u32, false).cell_pair();
+        count.set(count.get() + 1);
+        assert_eq!((count.get(), done.get()), (1, false));
+
+        done.set(true);
+        assert_eq!((count.get(), done.get()), (1, true));
+
+        let (x, y) = [1i8, 2].cell_pair();
+        x.swap(&y);
+        assert_eq!((x.get(), y.get()), (2, 1));
+    }
+
+    #[cfg(feature = "std")]
+    mod std_tests {
+        use super::*;
+
+        #[test]
+        fn test_boxed_slice_repeat() {
+            let buf = 0xAAu8.boxed_slice_repeat(16);
+            assert_eq!(buf.len(), 16);
+            assert!(buf.iter().all(|&b| b == 0xAA));
+            assert!(String::new().boxed_slice_repeat(0).is_empty());
+        }
+
+        #[test]
+        fn test_box_into_rc() {
+            let rc = Box::new(String::from("moved")).into_rc();
+            assert_eq!(*rc, "moved");
+
+            let slice: Rc<[u8]> = vec![1, 2, 3].into_boxed_slice().into_rc();
+            assert_eq!(slice.len(), 3);
+        }
+
+        #[test]
+        fn test_rc_pair_wrap() {
+            let (a, b) = 3u32.rc_pair();
+            assert_eq!(Rc::strong_count(&a), 2);
+            assert!(Rc::ptr_eq(&a, &b));
+        }
+
+        #[test]
+        fn test_refcell_wrap() {
+            let r = 5u32.refcell();
+            *r.borrow_mut() = 8;
+            assert_eq!(*r.borrow(), 8);
+        }
+
+        #[test]
+        fn test_rc_wrap() {
+            let rc = 42u32.rc();
+            assert_eq!(*rc, 42);
+            let rc2 = core::rc::Rc::clone(&rc);
+            assert_eq!(core::rc::Rc::strong_count(&rc2), 2);
+        }
+
+        #[cfg(feature = "allocator_api")]
+        #[test]
+        fn test_try_boxed_and_try_rc() {
+            let boxed = vec![1u8, 2, 3].try_boxed().unwrap();
+            assert_eq!(*boxed, [1, 2, 3]);
+
+            let rc = String::from("kept").try_rc().unwrap();
+            assert_eq!(*rc, "kept");
+            assert_eq!(core::rc::Rc::strong_count(&rc), 1);
+        }
+
+        #[test]
+        fn test_rc_refcell_wrap() {
+            let value = 100u32.rc_refcell();
+            *value.borrow_mut() = 200;
+            assert_eq!(*value.borrow(), 200);
+            let cloned = core::rc::Rc::clone(&value);
+            assert_eq!(core::rc::Rc::strong_count(&cloned), 2);
+        }
+
+        #[test]
+        fn test_into_dyn_error_with_source() {
+            let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing file");
+            let root = io.into_boxed_dyn_error();
+            let mid = "failed to load config".into_dyn_error_with_source(root);
+            let top = String::from("startup aborted").into_dyn_error_with_source(mid);
+
+            let mut chain = Vec::new();
+            let mut current: Option<&(dyn std::error::Error + 'static)> = Some(&*top);
+            while let Some(err) = current {
+                chain.push(err.to_string());
+                current = err.source();
+            }
+            assert_eq!(chain, ["startup aborted", "failed to load config", "missing file"]);
+        }
+
+        #[test]
+        fn test_refcell_swap_with() {
+            let a = String::from("a").refcell();
+            let b = String::from("b").refcell();
+            a.swap_with(&b);
+            assert_eq!((a.borrow().as_str(), b.borrow().as_str()), ("b", "a"));
+
+            // Self-swap must not attempt two mutable borrows.
+            a.swap_with(&a);
+            assert_eq!(*a.borrow(), "b");
+        }
+
+        #[test]
+        fn test_refcell_replace_with_fn() {
+            let state = 3u32.refcell();
+            let old = state.replace_with_fn(|n| *n * 2);
+            assert_eq!(old, 3);
+            assert_eq!(*state.borrow(), 6);
+        }
+
+        #[test]
+        fn test_refcell_try_with() {
+            let cell = vec![1, 2].refcell();
+            assert_eq!(cell.try_with(|v| v.len()).unwrap(), 2);
+            assert_eq!(cell.try_with_mut(|v| v.pop()).unwrap(), Some(2));
+
+            let writer = cell.borrow_mut();
+            assert!(cell.try_with(|v| v.len()).is_err());
+            assert!(cell.try_with_mut(|v| v.push(3)).is_err());
+            drop(writer);
+
+            let _reader = cell.borrow();
+            assert_eq!(cell.try_with(|v| v[0]).unwrap(), 1);
+            assert!(cell.try_with_mut(|v| v.clear()).is_err());
+        }
+
+        #[test]
+        fn test_refcell_with_mut_reports_caller() {
+            let cell = 0u32.refcell();
+            cell.with_mut(|n| *n += 1);
+            assert_eq!(*cell.borrow(), 1);
+
+            // Records its own call site, which `with_mut` reports as well
+            // since both are `#[track_caller]`.
+            #[track_caller]
+            fn bump(cell: &RefCell<u32>, site: &Cell<Option<&'static std::panic::Location<'static>>>) {
+                site.set(Some(std::panic::Location::caller()));
+                cell.with_mut(|n| *n += 1)
+            }
+
+            let _reader = cell.borrow();
+            let site = Cell::new(None);
+            let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| bump(&cell, &site)))
+                .unwrap_err();
+            let message = err.downcast_ref::<String>().unwrap();
+            assert!(message.starts_with("RefCell already borrowed"), "{}", message);
+            assert!(message.ends_with(&site.get().unwrap().to_string()), "{}", message);
+        }
+
+        #[test]
+        fn test_thread_local_cell_per_thread() {
+            crate::thread_local_cell!(COUNTER: u32 = 10);
+
+            COUNTER.with_borrow_mut(|n| *n += 1);
+            let handles: Vec<_> = (1..=2u32)
+                .map(|step| {
+                    std::thread::spawn(move || {
+                        COUNTER.with_borrow_mut(|n| *n += step * 100);
+                        COUNTER.with_borrow(|n| *n)
+                    })
+                })
+                .collect();
+            let seen: Vec<u32> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+            assert_eq!(seen, [110, 210]);
+            assert_eq!(COUNTER.with_borrow(|n| *n), 11);
+        }
+
+        #[test]
+        fn test_const_cell_thread_local_static() {
+            std::thread_local! {
+                static TICKS: Cell<u64> = const { const_cell(0) };
+            }
+
+            for _ in 0..3 {
+                TICKS.set(TICKS.get() + 1);
+            }
+            assert_eq!(TICKS.get(), 3);
+        }
+
+        #[test]
+        fn test_rc_refcell_default() {
+            let names = rc_refcell_default::<Vec<String>>();
+            assert!(names.borrow().is_empty());
+            names.borrow_mut().push("a".into());
+            assert_eq!(names.borrow().len(), 1);
+        }
+
+        #[test]
+        fn test_rc_refcell_vec_with_capacity() {
+            let children = rc_refcell_vec_with_capacity::<u32>(16);
+            assert!(children.borrow().capacity() >= 16);
+            assert!(children.borrow().is_empty());
+
+            let before = children.borrow().as_ptr();
+            children.borrow_mut().extend(0..16);
+            assert_eq!(children.borrow().as_ptr(), before);
+        }
+
+        #[test]
+        fn test_rc_weak_ext() {
+            let node = 5u32.rc();
+            let weak = Rc::downgrade(&node);
+            assert!(!weak.is_dangling());
+            assert!(Rc::ptr_eq(&weak.upgrade_or(|| unreachable!()), &node));
+
+            drop(node);
+            assert!(weak.is_dangling());
+            assert_eq!(*weak.upgrade_or(|| Rc::new(0)), 0);
+            assert!(std::rc::Weak::<u32>::new().is_dangling());
+        }
+
+        #[test]
+        fn test_into_local_generic() {
+            fn push_twice<T: IntoLocal>(value: T, edit: fn(&mut T)) -> T::Local {
+                let local = value.into_local();
+                let alias = local.clone();
+                T::with_local(&alias, edit);
+                T::with_local(&local, edit);
+                local
+            }
+
+            let list = push_twice(vec![0u8], |v| v.push(1));
+            assert_eq!(*list.borrow(), [0, 1, 1]);
+            assert_eq!(Rc::strong_count(&list), 1);
+        }
+
+        #[test]
+        fn test_cell_option_move_in_out() {
+            let slot = String::from("taken").cell_option();
+            assert_eq!(slot.take_value(), Some(String::from("taken")));
+            assert_eq!(slot.take_value(), None);
+
+            slot.put(String::from("new"));
+            assert_eq!(slot.replace_value(String::from("newer")), Some(String::from("new")));
+            assert_eq!(slot.into_inner(), Some(String::from("newer")));
+        }
+
+        #[test]
+        fn test_scoped_set_restores_after_panic() {
+            let depth = 0u32.cell();
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                let outer = depth.scoped_set(1);
+                let _inner = depth.scoped_set(2);
+                assert_eq!((outer.previous(), depth.get()), (0, 2));
+                panic!("scope failed");
+            }));
+
+            assert!(result.is_err());
+            assert_eq!(depth.get(), 0);
+        }
+
+        #[test]
+        fn test_cell_move_non_copy() {
+            let name = String::from("first").cell_move();
+            assert_eq!(name.replace(String::from("second")), "first");
+            assert_eq!(name.take(), "second");
+            name.set(String::from("third"));
+            assert_eq!(name.into_inner(), "third");
+        }
+
+        #[test]
+        fn test_array_boxed_and_rc_slice() {
+            let boxed = [1u16, 2, 3, 4].boxed_slice();
+            assert_eq!(boxed.len(), 4);
+            assert_eq!(&*boxed, [1, 2, 3, 4]);
+
+            let names = [String::from("a"), String::from("b"), String::from("c"), String::from("d")];
+            let shared = names.rc_slice();
+            assert_eq!(shared.len(), 4);
+            assert_eq!(shared[3], "d");
+        }
+
+        #[test]
+        fn test_collect_rc_slice() {
+            let evens = (0..10).filter(|n| n % 2 == 0).collect_rc_slice();
+            assert_eq!(evens.len(), 5);
+            assert_eq!(&*evens, [0, 2, 4, 6, 8]);
+
+            let empty = core::iter::empty::<String>().collect_rc_slice();
+            assert!(empty.is_empty());
+        }
+
+        #[test]
+        fn test_cell_slice_mutates_elements() {
+            let names = vec![String::from("a"), String::from("b")].cell_slice();
+            let view = names.as_cell_slice();
+            assert_eq!(view[0].replace(String::from("x")), "a");
+            view[1].set(String::from("y"));
+            assert_eq!(view.iter().map(Cell::take).collect::<Vec<_>>(), ["x", "y"]);
+
+            let empty = Vec::<u8>::new().cell_slice();
+            assert!(empty.as_cell_slice().is_empty());
+        }
+
+        #[test]
+        fn test_rc_same_as_and_by_address() {
+            let a = vec![1u8].rc();
+            let b = vec![1u8].rc();
+            assert!(a.same_as(&Rc::clone(&a)));
+            assert!(!a.same_as(&b));
+
+            let nodes = [Rc::clone(&a), Rc::clone(&b), Rc::clone(&a)];
+            let unique: std::collections::HashSet<_> = nodes.into_iter().map(ByAddress).collect();
+            assert_eq!(unique.len(), 2);
+            assert!(unique.contains(&ByAddress(b)));
+        }
+
+        #[test]
+        fn test_into_rc_elements_independent_counts() {
+            let words = vec![String::from("x"), String::from("y"), String::from("z")].into_rc_elements();
+            assert_eq!(words.len(), 3);
+
+            let kept = Rc::clone(&words[1]);
+            assert_eq!(Rc::strong_count(&words[0]), 1);
+            assert_eq!(Rc::strong_count(&words[1]), 2);
+            drop(words);
+            assert_eq!(*kept, "y");
+            assert_eq!(Rc::strong_count(&kept), 1);
+        }
+
+        #[cfg(feature = "sync")]
+        mod sync_tests {
+            use super::*;
+            use std::thread;
+
+            #[test]
+            fn test_arc_wrap() {
+                let arc = 99u32.arc();
+                assert_eq!(*arc, 99);
+                let arc2 = std::sync::Arc::clone(&arc);
+                assert_eq!(std::sync::Arc::strong_count(&arc2), 2);
+            }
+
+            #[cfg(feature = "allocator_api")]
+            #[test]
+            fn test_try_arc() {
+                let arc = [7u64; 16].try_arc().unwrap();
+                assert_eq!(arc[15], 7);
+                assert_eq!(std::sync::Arc::strong_count(&arc), 1);
+            }
+
+            #[test]
+            fn test_arc_pair_wrap() {
+                let (a, b) = 7u32.arc_pair();
+                assert_eq!(std::sync::Arc::strong_count(&a), 2);
+                assert!(std::sync::Arc::ptr_eq(&a, &b));
+            }
+
+            #[test]
+            fn test_tracked_mutex_wrap() {
+                let value = vec![1u32].tracked_mutex();
+                let cloned = std::sync::Arc::clone(&value);
+                thread::spawn(move || cloned.lock().unwrap().push(2))
+                    .join()
+                    .unwrap();
+                assert_eq!(*value.lock().unwrap(), [1, 2]);
+            }
+
+            #[test]
+            fn test_arc_mutex_init_wrap() {
+                let routes = Vec::new().arc_mutex_init(|routes| {
+                    routes.push("/health");
+                    routes.push("/metrics");
+                });
+                assert_eq!(Arc::strong_count(&routes), 1);
+                assert_eq!(*routes.lock().unwrap(), ["/health", "/metrics"]);
+            }
+
+            #[test]
+            fn test_arc_mutex_wrap() {
+                let value = 1u32.arc_mutex();
+                {
+                    let mut lock = value.lock().unwrap();
+                    *lock = 10;
+                }
+                assert_eq!(*value.lock().unwrap(), 10);
+
+                let cloned = std::sync::Arc::clone(&value);
+                let handle = thread::spawn(move || {
+                    let mut data = cloned.lock().unwrap();
+                    *data += 5;
+                });
+                handle.join().unwrap();
+                assert_eq!(*value.lock().unwrap(), 15);
+            }
+
+            #[test]
+            fn test_arc_rwlock_wrap() {
+                let value = 1u32.arc_rwlock();
+                {
+                    let mut write = value.write().unwrap();
+                    *write = 10;
+                }
+                assert_eq!(*value.read().unwrap(), 10);
+            }
+
+            #[test]
+            fn test_once_lock_wrap() {
+                let once = 42u32.once_lock();
+                assert_eq!(*once.get().unwrap(), 42);
+            }
+
+            #[cfg(not(feature = "loom"))]
+            #[test]
+            fn test_atomic_cell_wrap() {
+                let pair = [1u16, 2].atomic_cell();
+                assert!(AtomicCell::<[u16; 2]>::is_lock_free());
+                pair.store([3, 4]);
+                assert_eq!(pair.load(), [3, 4]);
+            }
+
+            #[test]
+            #[allow(clippy::bool_assert_comparison)]
+            fn test_atomic_wrappers() {
                 use core::sync::atomic::Ordering;
 
                 // Test u32 -> various atomic types
@@ -401,6 +4426,853 @@ mod tests {
                 a_bool.store(false, Ordering::SeqCst);
                 assert_eq!(a_bool.load(Ordering::SeqCst), false);
             }
+
+            #[test]
+            fn test_atomic_ptr_null() {
+                let ptr = core::ptr::null_mut::<u32>().atomic_ptr();
+                assert!(ptr.load(Ordering::SeqCst).is_null());
+            }
+
+            #[test]
+            fn test_atomic_ptr_box_round_trip() {
+                let first = Box::into_raw(Box::new(1u32));
+                let second = Box::into_raw(Box::new(2u32));
+                let ptr = first.atomic_ptr();
+
+                assert_eq!(ptr.compare_exchange_ptr(second, second), Err(first));
+                assert_eq!(ptr.compare_exchange_ptr(first, second), Ok(first));
+                assert_eq!(ptr.swap_ptr(core::ptr::null_mut()), second);
+                assert!(ptr.load(Ordering::SeqCst).is_null());
+
+                // SAFETY: both pointers came from `Box::into_raw` and are no
+                // longer reachable through the atomic.
+                unsafe {
+                    assert_eq!(*Box::from_raw(first), 1);
+                    assert_eq!(*Box::from_raw(second), 2);
+                }
+            }
+
+            #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+            #[test]
+            fn test_saturating_atomic_wrappers() {
+                assert_eq!(300u32.sat_atomic_u8().load(Ordering::SeqCst), 255);
+                assert_eq!(200u8.sat_atomic_u8().load(Ordering::SeqCst), 200);
+                assert_eq!((-7i32).sat_atomic_u8().load(Ordering::SeqCst), 0);
+                assert_eq!((-7i32).sat_atomic_u64().load(Ordering::SeqCst), 0);
+                assert_eq!(i64::MIN.sat_atomic_i16().load(Ordering::SeqCst), i16::MIN);
+                assert_eq!(u64::MAX.sat_atomic_i64().load(Ordering::SeqCst), i64::MAX);
+                assert_eq!(u64::MAX.sat_atomic_u64().load(Ordering::SeqCst), u64::MAX);
+                assert_eq!((-1isize).sat_atomic_isize().load(Ordering::SeqCst), -1);
+            }
+
+            #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+            #[test]
+            fn test_atomic_array_wrap() {
+                let empty: [AtomicU32; 0] = [0u32; 0].atomic_array();
+                assert!(empty.is_empty());
+
+                let single = [true].atomic_array();
+                assert!(single[0].load(Ordering::SeqCst));
+
+                let values = [1i64, -2, 3, -4, 5, -6, 7, -8];
+                let atomics = values.atomic_array();
+                for (atomic, value) in atomics.iter().zip(values) {
+                    assert_eq!(atomic.load(Ordering::SeqCst), value);
+                }
+            }
+
+            #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+            #[test]
+            fn test_atomic_time_wrappers() {
+                let timeout = Duration::from_millis(1500).atomic_duration();
+                assert_eq!(timeout.load(Ordering::SeqCst), Duration::from_millis(1500));
+
+                let start = Instant::now();
+                let seen = start.atomic_instant();
+                assert_eq!(seen.load(Ordering::SeqCst), start);
+            }
+
+            #[test]
+            fn test_arc_pin_wrap() {
+                let pinned = 5u32.arc_pin();
+                let other = Pin::clone(&pinned);
+                assert_eq!(*other, 5);
+                assert!(core::ptr::eq(&*pinned, &*other));
+            }
+
+            #[test]
+            fn test_pin_arc_shared_pinned() {
+                struct SelfAddressed {
+                    value: u32,
+                    _pin: core::marker::PhantomPinned,
+                }
+
+                let pinned = SelfAddressed {
+                    value: 7,
+                    _pin: core::marker::PhantomPinned,
+                }
+                .pin_arc();
+                let other = Pin::clone(&pinned);
+                assert_eq!(other.value, 7);
+                assert!(core::ptr::eq(&*pinned, &*other));
+
+                // Only shared access is available for a `!Unpin` value: it
+                // moves into the other thread as a `Pin<Arc<_>>` pointing at
+                // the same address, never as the value itself.
+                let addr = &*pinned as *const SelfAddressed as usize;
+                let seen = thread::spawn(move || &*other as *const SelfAddressed as usize)
+                    .join()
+                    .unwrap();
+                assert_eq!(seen, addr);
+            }
+
+            #[test]
+            fn test_lock_spin_mutual_exclusion() {
+                let counter = 0u64.arc_mutex();
+                thread::scope(|s| {
+                    for _ in 0..2 {
+                        s.spawn(|| {
+                            for _ in 0..10_000 {
+                                let mut guard = counter.lock_spin(16);
+                                // A non-atomic read-modify-write: a lost update
+                                // would show up as a short count.
+                                let seen = *guard;
+                                *guard = seen + 1;
+                            }
+                        });
+                    }
+                });
+                assert_eq!(*counter.lock_spin(0), 20_000);
+            }
+
+            #[test]
+            fn test_lock_spin_falls_back_to_blocking() {
+                let value = 1u32.arc_mutex();
+                let held = value.lock().unwrap();
+                let waiter = {
+                    let value = Arc::clone(&value);
+                    thread::spawn(move || *value.lock_spin(4))
+                };
+                thread::sleep(std::time::Duration::from_millis(10));
+                drop(held);
+                assert_eq!(waiter.join().unwrap(), 1);
+            }
+
+            #[test]
+            fn test_try_lock_opt_contended_then_free() {
+                let value = 1u32.arc_mutex();
+                let held = value.lock().unwrap();
+                let contended = thread::scope(|s| s.spawn(|| value.try_lock_opt().is_none()).join().unwrap());
+                assert!(contended);
+
+                drop(held);
+                let seen = thread::scope(|s| s.spawn(|| value.try_lock_opt().map(|g| *g)).join().unwrap());
+                assert_eq!(seen, Some(1));
+            }
+
+            #[test]
+            fn test_try_rwlock_opt_contended_then_free() {
+                let value = 1u32.arc_rwlock();
+                let reader = value.read().unwrap();
+                thread::scope(|s| {
+                    s.spawn(|| {
+                        assert!(value.try_read_opt().is_some());
+                        assert!(value.try_write_opt().is_none());
+                    });
+                });
+
+                drop(reader);
+                let writer = value.write().unwrap();
+                thread::scope(|s| {
+                    s.spawn(|| assert!(value.try_read_opt().is_none()));
+                });
+
+                drop(writer);
+                thread::scope(|s| {
+                    s.spawn(|| *value.try_write_opt().unwrap() = 2);
+                });
+                assert_eq!(*value.read().unwrap(), 2);
+            }
+
+            #[test]
+            fn test_try_lock_opt_ignores_poison() {
+                let value = 0u8.arc_mutex();
+                let cloned = Arc::clone(&value);
+                let _ = thread::spawn(move || {
+                    let _guard = cloned.lock().unwrap();
+                    panic!("poison the mutex");
+                })
+                .join();
+                assert!(value.is_poisoned());
+                assert!(value.try_lock_opt().is_some());
+            }
+
+            #[test]
+            fn test_mutex_poison_recovery() {
+                let value = vec![1u32].arc_mutex();
+                let cloned = Arc::clone(&value);
+                let result = thread::spawn(move || {
+                    let mut guard = cloned.lock().unwrap();
+                    guard.push(2);
+                    panic!("poison the mutex");
+                })
+                .join();
+                assert!(result.is_err());
+                assert!(value.is_poisoned());
+
+                // The half-finished update is still visible.
+                assert_eq!(*value.lock_ignore_poison(), [1, 2]);
+                assert!(value.is_poisoned());
+
+                value.lock_clear_poison().push(3);
+                assert!(!value.is_poisoned());
+                assert_eq!(*value.lock().unwrap(), [1, 2, 3]);
+            }
+
+            #[test]
+            fn test_rwlock_poison_recovery() {
+                let value = 1u32.arc_rwlock();
+                let cloned = Arc::clone(&value);
+                let result = thread::spawn(move || {
+                    let mut guard = cloned.write().unwrap();
+                    *guard = 2;
+                    panic!("poison the rwlock");
+                })
+                .join();
+                assert!(result.is_err());
+                assert!(value.read().is_err());
+
+                assert_eq!(*value.read_ignore_poison(), 2);
+                *value.write_ignore_poison() = 3;
+                assert!(value.is_poisoned());
+
+                *value.write_clear_poison() += 1;
+                assert_eq!(*value.read().unwrap(), 4);
+            }
+
+            #[test]
+            fn test_snapshot_under_concurrent_writes() {
+                let mutex = vec![0u32; 16].arc_mutex();
+                let rwlock = vec![0u32; 16].arc_rwlock();
+
+                let (m, r) = (Arc::clone(&mutex), Arc::clone(&rwlock));
+                let writer = thread::spawn(move || {
+                    for i in 1..=500 {
+                        m.lock_ignore_poison().iter_mut().for_each(|x| *x = i);
+                        r.write_ignore_poison().iter_mut().for_each(|x| *x = i);
+                    }
+                });
+
+                for _ in 0..500 {
+                    let a = mutex.snapshot();
+                    let b = rwlock.snapshot();
+                    assert!(a.iter().all(|&x| x == a[0]), "torn mutex snapshot: {:?}", a);
+                    assert!(b.iter().all(|&x| x == b[0]), "torn rwlock snapshot: {:?}", b);
+                }
+                writer.join().unwrap();
+
+                assert_eq!(mutex.snapshot(), vec![500; 16]);
+                assert_eq!(rwlock.snapshot(), vec![500; 16]);
+            }
+
+            #[test]
+            fn test_arc_defaults() {
+                let count = arc_default::<u64>();
+                assert_eq!(*count, u64::default());
+
+                let bytes = arc_mutex_default::<Vec<u8>>();
+                assert_eq!(*bytes.lock().unwrap(), Vec::<u8>::default());
+                bytes.lock().unwrap().extend([1, 2]);
+                assert_eq!(bytes.snapshot(), [1, 2]);
+            }
+
+            #[test]
+            fn test_arc_mutex_vec_with_capacity() {
+                let queue = arc_mutex_vec_with_capacity::<String>(8);
+                assert!(queue.lock().unwrap().capacity() >= 8);
+                assert!(queue.lock().unwrap().is_empty());
+            }
+
+            #[test]
+            fn test_arc_weak_ext() {
+                let node = String::from("live").arc();
+                let weak = Arc::downgrade(&node);
+                assert!(!weak.is_dangling());
+                assert_eq!(*weak.upgrade_or(|| unreachable!()), "live");
+
+                drop(node);
+                assert!(weak.is_dangling());
+                let mut called = false;
+                let fallback = weak.upgrade_or(|| {
+                    called = true;
+                    Arc::new(String::from("fallback"))
+                });
+                assert!(called);
+                assert_eq!(*fallback, "fallback");
+            }
+
+            #[test]
+            fn test_box_into_arc() {
+                let arc = Box::new(vec![1u8, 2]).into_arc();
+                assert_eq!(*arc, [1, 2]);
+
+                let slice: Arc<[u32]> = vec![7; 5].into_boxed_slice().into_arc();
+                assert_eq!(slice.len(), 5);
+                assert!(slice.iter().all(|&n| n == 7));
+
+                let text: Arc<str> = Box::<str>::from("hello").into_arc();
+                assert_eq!(&*text, "hello");
+            }
+
+            #[cfg(feature = "bytemuck")]
+            #[test]
+            fn test_pod_atomic_round_trip() {
+                #[derive(Clone, Copy, Debug, PartialEq)]
+                #[repr(C)]
+                struct Pair {
+                    a: u32,
+                    b: f32,
+                }
+                // SAFETY: `Pair` is `repr(C)` with two 4-byte fields, so it has
+                // no padding and every bit pattern is valid.
+                unsafe impl bytemuck::Zeroable for Pair {}
+                unsafe impl bytemuck::Pod for Pair {}
+
+                let bytes = [1u8, 2, 3, 4, 5, 6, 7, 8];
+                let atomic = bytes.atomic_bits_u64();
+                assert_eq!(atomic.load_pod::<[u8; 8]>(Ordering::SeqCst), bytes);
+
+                let pair = Pair { a: 7, b: -1.5 };
+                assert_eq!(atomic.swap_pod([0u8; 8], Ordering::SeqCst), bytes);
+                atomic.store_pod(pair, Ordering::SeqCst);
+
+                let back: Pair = atomic.load_pod(Ordering::SeqCst);
+                assert_eq!(back, pair);
+                assert_eq!(bytemuck::bytes_of(&back), bytemuck::bytes_of(&pair));
+            }
+
+            #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+            #[test]
+            fn test_atomic_ord_publish_consume() {
+                let data = Arc::new(0u64.atomic_u64());
+                let ready = Arc::new(0u32.atomic_u32());
+
+                let (d, r) = (Arc::clone(&data), Arc::clone(&ready));
+                let producer = thread::spawn(move || {
+                    d.store_relaxed(42);
+                    r.store_rel(1);
+                });
+
+                while ready.load_acq() == 0 {
+                    std::hint::spin_loop();
+                }
+                // The acquire load synchronizes with the release store, so the
+                // relaxed write before it must be visible.
+                assert_eq!(data.load_relaxed(), 42);
+                producer.join().unwrap();
+
+                assert_eq!(data.fetch_add_relaxed(8), 42);
+                assert_eq!(data.fetch_sub_acq_rel(50), 50);
+                assert_eq!(data.swap_acq_rel(7), 0);
+                assert_eq!(data.fetch_add_acq_rel(1), 7);
+                assert_eq!(data.fetch_sub_relaxed(1), 8);
+                data.store_with(3, Orderings::SEQ_CST);
+                assert_eq!(data.load_with(Orderings::RELAXED), 3);
+                assert_eq!(Orderings::ACQ_REL.load, Ordering::Acquire);
+                assert_eq!(Orderings::ACQ_REL.store, Ordering::Release);
+            }
+
+            #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+            #[test]
+            fn test_atomic_cas() {
+                let value = 10i64.atomic_i64();
+                assert_eq!(value.cas(10, 20), Ok(10));
+                assert_eq!(value.cas(10, 30), Err(20));
+                assert_eq!(value.load(Ordering::SeqCst), 20);
+
+                let mut current = value.load(Ordering::Relaxed);
+                while let Err(actual) = value.cas_weak(current, current + 1) {
+                    current = actual;
+                }
+                assert_eq!(value.load(Ordering::SeqCst), 21);
+            }
+
+            #[cfg(feature = "portable-atomic")]
+            #[test]
+            fn test_atomic_u128_cas_tagged_pointer() {
+                // A pointer in the low half and a version tag in the high half,
+                // updated together so a stale pointer can't win an ABA race.
+                let slot = (1u128 << 64 | 0x1000).atomic_u128();
+                let old = slot.load(Ordering::Acquire);
+                assert_eq!(slot.cas(old, 2 << 64 | 0x2000), Ok(old));
+                assert_eq!(slot.cas(old, 3 << 64 | 0x1000), Err(2 << 64 | 0x2000));
+
+                thread::scope(|s| {
+                    for _ in 0..4 {
+                        s.spawn(|| {
+                            for _ in 0..1000 {
+                                let mut current = slot.load(Ordering::Relaxed);
+                                while let Err(actual) = slot.cas_weak(current, current + (1 << 64)) {
+                                    current = actual;
+                                }
+                            }
+                        });
+                    }
+                });
+                assert_eq!(slot.into_value(), 4002 << 64 | 0x2000);
+
+                let signed = (-1i32).atomic_i128();
+                assert_eq!(signed.cas(-1, i128::MIN), Ok(-1));
+                assert_eq!(signed.max(i128::MAX), i128::MIN);
+                assert_eq!(u128::MAX.atomic_u64().load(Ordering::Relaxed), u64::MAX);
+            }
+
+            #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+            #[test]
+            fn test_atomic_scoped_set_restores_after_panic() {
+                let busy = false.atomic_bool();
+                let level = 3i64.atomic_i64();
+                let result = std::panic::catch_unwind(|| {
+                    let _busy = busy.scoped_set(true);
+                    let _level = level.scoped_set(-1);
+                    assert!(busy.load(Ordering::SeqCst));
+                    panic!("scope failed");
+                });
+
+                assert!(result.is_err());
+                assert!(!busy.load(Ordering::SeqCst));
+                assert_eq!(level.load(Ordering::SeqCst), 3);
+
+                let guard = level.scoped_set(7);
+                assert_eq!(level.load(Ordering::SeqCst), 7);
+                drop(guard);
+                assert_eq!(level.load(Ordering::SeqCst), 3);
+            }
+
+            #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+            #[test]
+            fn test_char_atomic_round_trip() {
+                let c = 'x'.atomic_u32();
+                assert_eq!(c.load_char(Ordering::SeqCst), 'x');
+
+                c.store_char('€', Ordering::SeqCst);
+                assert_eq!(c.load(Ordering::SeqCst), 0x20AC);
+                assert_eq!(c.swap_char('x', Ordering::SeqCst), '€');
+                assert_eq!(c.load_char(Ordering::SeqCst), 'x');
+                assert_eq!('€'.atomic_u64().load(Ordering::SeqCst), 0x20AC);
+            }
+
+            #[test]
+            #[should_panic(expected = "not a Unicode scalar value")]
+            fn test_char_atomic_rejects_surrogate() {
+                let c = 'a'.atomic_u32();
+                c.store(0xD800, Ordering::SeqCst);
+                c.load_char(Ordering::SeqCst);
+            }
+
+            #[test]
+            fn test_atomic_min_max_across_threads() {
+                let peak = 0u32.atomic_u32();
+                let low = u32::MAX.atomic_u32();
+                thread::scope(|s| {
+                    for t in 0..4u32 {
+                        let (peak, low) = (&peak, &low);
+                        s.spawn(move || {
+                            for i in 0..1000u32 {
+                                let sample = (i * 7919 + t * 104_729) % 50_000 + 1;
+                                peak.max(sample);
+                                low.min(sample);
+                            }
+                        });
+                    }
+                });
+
+                let samples = (0..4u32)
+                    .flat_map(|t| (0..1000u32).map(move |i| (i * 7919 + t * 104_729) % 50_000 + 1));
+                let (lo, hi) = samples.fold((u32::MAX, 0), |(lo, hi), v| (lo.min(v), hi.max(v)));
+                assert_eq!(peak.load(Ordering::SeqCst), hi);
+                assert_eq!(low.load(Ordering::SeqCst), lo);
+
+                let signed = core::sync::atomic::AtomicI8::new(-5);
+                assert_eq!(signed.min(-100), -5);
+                assert_eq!(signed.max(3), -100);
+                assert_eq!(signed.load(Ordering::SeqCst), 3);
+            }
+
+            #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+            #[test]
+            fn test_into_value_after_threads() {
+                let counter = Arc::new(0u32.atomic_u32());
+                thread::scope(|s| {
+                    for _ in 0..4 {
+                        s.spawn(|| {
+                            for _ in 0..100 {
+                                counter.fetch_add(1, Ordering::Relaxed);
+                            }
+                        });
+                    }
+                });
+                let counter = Arc::try_unwrap(counter).unwrap();
+                assert_eq!(counter.into_value(), 400);
+
+                let flag = false.atomic_bool();
+                flag.store(true, Ordering::Relaxed);
+                assert!(flag.into_value());
+                assert_eq!((-4i64).atomic_i64().into_value(), -4);
+            }
+
+            fn append_on_thread<T: IntoShared>(value: T, edit: fn(&mut T)) -> T::Shared {
+                let shared = value.into_shared();
+                thread::scope(|s| {
+                    s.spawn(|| T::with_shared(&shared, edit));
+                });
+                shared
+            }
+
+            #[test]
+            fn test_into_shared_generic() {
+                let number = append_on_thread(41i32, |n| *n += 1);
+                assert_eq!(i32::with_shared(&number, |n| *n), 42);
+
+                let text = append_on_thread(String::from("ab"), |s| s.push('c'));
+                assert_eq!(String::with_shared(&text, |s| s.clone()), "abc");
+                assert_eq!(Arc::strong_count(&text), 1);
+            }
+
+            #[test]
+            fn test_map_insert_arc_shares_refcount() {
+                let mut cache = std::collections::HashMap::new();
+                let first = cache.insert_arc(1u32, vec![1u8]);
+                assert_eq!(Arc::strong_count(&first), 2);
+                assert!(Arc::ptr_eq(&first, &cache[&1]));
+
+                let replaced = cache.insert_arc(1, vec![2]);
+                assert_eq!(Arc::strong_count(&first), 1);
+                assert_eq!(Arc::strong_count(&replaced), 2);
+            }
+
+            #[test]
+            fn test_map_get_or_insert_arc() {
+                let mut cache = std::collections::HashMap::new();
+                let mut calls = 0;
+                let built = cache.get_or_insert_arc("k", || {
+                    calls += 1;
+                    String::from("v")
+                });
+                assert_eq!(Arc::strong_count(&built), 2);
+
+                let cached = cache.get_or_insert_arc("k", || {
+                    calls += 1;
+                    String::from("other")
+                });
+                assert!(Arc::ptr_eq(&built, &cached));
+                assert_eq!(calls, 1);
+                assert_eq!(Arc::strong_count(&built), 3);
+            }
+
+            #[test]
+            fn test_array_arc_slice() {
+                let shared = [10u64, 20, 30, 40].arc_slice();
+                let other = Arc::clone(&shared);
+                assert_eq!(other.len(), 4);
+                assert_eq!(&*other, [10, 20, 30, 40]);
+                assert!(Arc::ptr_eq(&shared, &other));
+            }
+
+            #[test]
+            fn test_collect_arc_slice() {
+                let odds = (0..10u32).filter(|n| n % 2 == 1).collect_arc_slice();
+                assert_eq!(odds.len(), 5);
+                assert_eq!(&*odds, [1, 3, 5, 7, 9]);
+
+                let names = ["a", "b"].iter().map(|s| s.to_uppercase()).collect_arc_slice();
+                assert_eq!(&*names, ["A", "B"]);
+                assert_eq!(Arc::strong_count(&names), 1);
+            }
+
+            #[test]
+            fn test_arc_same_as_and_by_address_keys() {
+                let a = String::from("x").arc();
+                let b = String::from("x").arc();
+                assert!(a.same_as(&a.clone()));
+                assert!(!a.same_as(&b));
+
+                let mut visits = std::collections::HashMap::new();
+                for node in [&a, &b, &a, &a] {
+                    *visits.entry(ByAddress(Arc::clone(node))).or_insert(0) += 1;
+                }
+                assert_eq!(visits[&ByAddress(a)], 3);
+                assert_eq!(visits[&ByAddress(b)], 1);
+            }
+
+            #[test]
+            fn test_into_arc_elements_fan_out() {
+                let inputs = vec![vec![1u32, 2], vec![3], vec![]].into_arc_elements();
+                assert_eq!(inputs.len(), 3);
+
+                let sums: Vec<u32> = thread::scope(|s| {
+                    let handles: Vec<_> = inputs
+                        .iter()
+                        .map(|input| {
+                            let input = Arc::clone(input);
+                            s.spawn(move || input.iter().sum())
+                        })
+                        .collect();
+                    handles.into_iter().map(|h| h.join().unwrap()).collect()
+                });
+
+                assert_eq!(sums, [3, 3, 0]);
+                assert!(inputs.iter().all(|input| Arc::strong_count(input) == 1));
+            }
+
+            #[test]
+            fn test_vec_concurrent_queue_drains_in_order() {
+                let queue = vec![1u32, 2, 3].concurrent_queue();
+                let drained: Vec<u32> = thread::scope(|s| {
+                    let worker = s.spawn(|| (0..4).map(|_| queue.pop_front_blocking()).collect());
+                    queue.push_back(4);
+                    worker.join().unwrap()
+                });
+                assert_eq!(drained, [1, 2, 3, 4]);
+                assert!(queue.is_empty());
+            }
+
+            #[test]
+            fn test_static_wrap_initializes_once() {
+                use std::sync::atomic::AtomicUsize;
+                use std::sync::Barrier;
+
+                static RUNS: AtomicUsize = AtomicUsize::new(0);
+                crate::static_wrap!(CONFIG: Arc<Vec<u32>> = {
+                    RUNS.fetch_add(1, Ordering::SeqCst);
+                    vec![1, 2, 3]
+                });
+
+                let barrier = Barrier::new(8);
+                let seen: Vec<usize> = thread::scope(|s| {
+                    let handles: Vec<_> = (0..8)
+                        .map(|_| {
+                            s.spawn(|| {
+                                barrier.wait();
+                                Arc::as_ptr(CONFIG()) as usize
+                            })
+                        })
+                        .collect();
+                    handles.into_iter().map(|h| h.join().unwrap()).collect()
+                });
+
+                assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+                assert!(seen.iter().all(|&addr| addr == seen[0]));
+                assert_eq!(**CONFIG(), [1, 2, 3]);
+            }
+
+            #[test]
+            fn test_once_lock_get_or_default() {
+                let lock: OnceLock<Vec<u8>> = OnceLock::new();
+                assert!(lock.get_or_default().is_empty());
+                assert!(lock.get().is_some());
+
+                let filled = vec![1u8].once_lock();
+                assert_eq!(filled.get_or_default(), &[1]);
+            }
+
+            #[test]
+            fn test_once_lock_try_wrap_retries_after_error() {
+                let lock: OnceLock<String> = OnceLock::new();
+                assert_eq!(lock.get_or_try_wrap(|| Err::<String, _>("offline")), Err("offline"));
+                assert!(lock.get().is_none());
+
+                let value = lock.get_or_try_wrap(|| Ok::<_, &str>(String::from("ready")));
+                assert_eq!(value.map(String::as_str), Ok("ready"));
+
+                let cached = lock.get_or_try_wrap(|| -> Result<String, &str> { unreachable!() });
+                assert_eq!(cached.map(String::as_str), Ok("ready"));
+            }
+
+            #[test]
+            fn test_arc_any_round_trip() {
+                use std::any::{Any, TypeId};
+                use std::collections::HashMap;
+
+                let mut store: HashMap<TypeId, Arc<dyn Any + Send + Sync>> = HashMap::new();
+                store.insert(TypeId::of::<String>(), String::from("db").arc_any());
+                store.insert(TypeId::of::<Vec<u16>>(), vec![1u16, 2].arc_any());
+
+                let name = downcast_arc::<String>(Arc::clone(&store[&TypeId::of::<String>()]));
+                assert_eq!(name.unwrap().as_str(), "db");
+                let ports = downcast_arc::<Vec<u16>>(Arc::clone(&store[&TypeId::of::<Vec<u16>>()]));
+                assert_eq!(*ports.unwrap(), [1, 2]);
+
+                let original = Arc::clone(&store[&TypeId::of::<String>()]);
+                let err = downcast_arc::<u64>(Arc::clone(&original)).unwrap_err();
+                assert!(Arc::ptr_eq(&err, &original));
+                assert!(err.is::<String>());
+            }
+        }
+
+        #[test]
+        fn test_cow_wrap() {
+            let owned = String::from("owned");
+            let owned_ptr = owned.as_ptr();
+            let cow = owned.cow_owned();
+            assert!(matches!(cow, Cow::Owned(_)));
+            // Unwrapping an owned Cow hands back the same allocation.
+            let recovered = cow.into_owned();
+            assert_eq!(recovered.as_ptr(), owned_ptr);
+
+            let source = "borrowed";
+            let cow = source.cow_borrowed();
+            assert!(matches!(cow, Cow::Borrowed("borrowed")));
+            // Unwrapping a borrowed Cow has to allocate a fresh String.
+            let copied = cow.into_owned();
+            assert_eq!(copied, source);
+            assert_ne!(copied.as_ptr(), source.as_ptr());
+
+            let slice: Cow<[u8]> = vec![1u8, 2].cow_owned();
+            assert_eq!(&*slice, &[1, 2]);
+        }
+
+        #[test]
+        fn test_box_rc_dyn() {
+            let boxed = crate::box_dyn!(dyn core::fmt::Display, 7u8);
+            assert_eq!(boxed.to_string(), "7");
+
+            let shared = crate::rc_dyn!(dyn Fn(i32) -> i32, |x| x * 2);
+            let again = Rc::clone(&shared);
+            assert_eq!(shared(2) + again(3), 10);
+        }
+
+        #[test]
+        fn test_rc_new_cyclic() {
+            struct Node {
+                me: std::rc::Weak<Node>,
+            }
+
+            let node = rc_new_cyclic(|me| Node { me: me.clone() });
+            assert!(Rc::ptr_eq(&node.me.upgrade().unwrap(), &node));
+
+            let weak = Rc::downgrade(&node);
+            drop(node);
+            assert!(weak.upgrade().is_none());
+        }
+
+        #[cfg(feature = "tokio")]
+        mod tokio_tests {
+            use super::*;
+            use std::sync::Arc;
+            use std::time::Duration;
+
+            #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+            async fn test_arc_tokio_mutex_across_await() {
+                let log = Vec::<u32>::new().arc_tokio_mutex();
+
+                let tasks: Vec<_> = (0..2u32)
+                    .map(|id| {
+                        let log = Arc::clone(&log);
+                        tokio::spawn(async move {
+                            let mut guard = log.lock().await;
+                            guard.push(id);
+                            // Holding the guard across an await must keep the other task out.
+                            tokio::time::sleep(Duration::from_millis(10)).await;
+                            guard.push(id);
+                        })
+                    })
+                    .collect();
+                for task in tasks {
+                    task.await.unwrap();
+                }
+
+                let log = log.lock().await;
+                assert_eq!(log.len(), 4);
+                assert_eq!(log[0], log[1]);
+                assert_eq!(log[2], log[3]);
+                assert_ne!(log[0], log[2]);
+            }
+
+            #[tokio::test]
+            async fn test_arc_tokio_rwlock() {
+                let value = 1u32.arc_tokio_rwlock();
+                {
+                    let mut write = value.write().await;
+                    tokio::task::yield_now().await;
+                    *write += 1;
+                }
+                let (a, b) = tokio::join!(value.read(), value.read());
+                assert_eq!((*a, *b), (2, 2));
+            }
+
+            #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+            async fn test_async_once_cell_single_initializer() {
+                use std::sync::atomic::{AtomicUsize, Ordering};
+
+                let cell = Arc::new(crate::async_once_cell::<u64>());
+                let runs = Arc::new(AtomicUsize::new(0));
+
+                let tasks: Vec<_> = (0..2)
+                    .map(|_| {
+                        let (cell, runs) = (Arc::clone(&cell), Arc::clone(&runs));
+                        tokio::spawn(async move {
+                            *cell
+                                .get_or_init(|| async {
+                                    runs.fetch_add(1, Ordering::SeqCst);
+                                    tokio::time::sleep(Duration::from_millis(10)).await;
+                                    7
+                                })
+                                .await
+                        })
+                    })
+                    .collect();
+                for task in tasks {
+                    assert_eq!(task.await.unwrap(), 7);
+                }
+                assert_eq!(runs.load(Ordering::SeqCst), 1);
+            }
+        }
+
+        #[cfg(feature = "parking_lot")]
+        mod parking_lot_tests {
+            use super::*;
+            use parking_lot::RwLockUpgradableReadGuard;
+            use std::sync::atomic::{AtomicBool, Ordering};
+            use std::sync::Arc;
+            use std::thread;
+            use std::time::Duration;
+
+            #[test]
+            fn test_upgrade_blocks_plain_reader() {
+                let shared = vec![1u32].arc_pl_rwlock();
+                let reader_done = Arc::new(AtomicBool::new(false));
+
+                let reader = shared.with_upgradable_read(|guard| {
+                    // Plain readers may share the lock with an upgradable read.
+                    assert_eq!(shared.try_read().map(|r| r.len()), Some(1));
+                    assert!(shared.try_upgradable_read().is_none());
+
+                    let mut write = RwLockUpgradableReadGuard::upgrade(guard);
+                    let (lock, done) = (Arc::clone(&shared), Arc::clone(&reader_done));
+                    let reader = thread::spawn(move || {
+                        let seen = lock.read().clone();
+                        done.store(true, Ordering::SeqCst);
+                        seen
+                    });
+
+                    thread::sleep(Duration::from_millis(20));
+                    assert!(!reader_done.load(Ordering::SeqCst));
+                    write.push(2);
+                    reader
+                });
+
+                assert_eq!(reader.join().unwrap(), [1, 2]);
+                assert!(reader_done.load(Ordering::SeqCst));
+            }
+
+            #[test]
+            fn test_upgradable_read_without_upgrade() {
+                let shared = 5u32.arc_pl_rwlock();
+                let doubled = shared.with_upgradable_read(|guard| *guard * 2);
+                assert_eq!(doubled, 10);
+                assert!(shared.try_write().is_some());
+            }
         }
     }
 }
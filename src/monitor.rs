@@ -0,0 +1,161 @@
+//! A `Mutex` paired with the `Condvar` that signals changes to its value.
+
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+
+use crate::MutexExt;
+
+/// A shared value plus a condition variable, for waiting until the value
+/// reaches some state.
+///
+/// [`wait_until`](Monitor::wait_until) takes the condition as a predicate
+/// and re-checks it under the lock before every wait and after every wakeup,
+/// so a notification sent before the waiter arrived is never lost and a
+/// spurious wakeup never returns early. [`notify_one`](Monitor::notify_one)
+/// and [`notify_all`](Monitor::notify_all) change the value and notify in
+/// one call, so a change can't be published without waking anyone. Clones
+/// are handles to the same monitor.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::WrapExt;
+///     use std::thread;
+///
+///     let loaded = 0u32.monitor();
+///     let loader = {
+///         let loaded = loaded.clone();
+///         thread::spawn(move || loaded.notify_all(|count| *count = 3))
+///     };
+///
+///     let count = loaded.wait_until(|count| *count == 3);
+///     assert_eq!(*count, 3);
+///     drop(count);
+///     loader.join().unwrap();
+/// }
+/// ```
+pub struct Monitor<T> {
+    state: Arc<(Mutex<T>, Condvar)>,
+}
+
+impl<T> Monitor<T> {
+    /// Creates a monitor holding `value`.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Monitor {
+            state: Arc::new((Mutex::new(value), Condvar::new())),
+        }
+    }
+
+    /// Locks the value without waiting for any condition.
+    ///
+    /// Changes made through this guard wake no one; use
+    /// [`notify_one`](Monitor::notify_one) or
+    /// [`notify_all`](Monitor::notify_all) for changes waiters care about.
+    #[inline]
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        self.state.0.lock_ignore_poison()
+    }
+
+    /// Blocks until `condition` returns `true` for the value, and returns
+    /// the guard it was checked under.
+    ///
+    /// Returns immediately if the condition already holds. Otherwise the
+    /// lock is released while waiting and `condition` is called again after
+    /// each wakeup, including spurious ones.
+    pub fn wait_until(&self, mut condition: impl FnMut(&T) -> bool) -> MutexGuard<'_, T> {
+        let (value, changed) = &*self.state;
+        let mut guard = value.lock_ignore_poison();
+        while !condition(&guard) {
+            guard = changed.wait(guard).unwrap_or_else(PoisonError::into_inner);
+        }
+        guard
+    }
+
+    /// Applies `update` to the value under the lock, then wakes one waiter.
+    ///
+    /// The lock is released before notifying, so the woken thread doesn't
+    /// immediately block on it again.
+    pub fn notify_one<R>(&self, update: impl FnOnce(&mut T) -> R) -> R {
+        let (value, changed) = &*self.state;
+        let result = update(&mut value.lock_ignore_poison());
+        changed.notify_one();
+        result
+    }
+
+    /// Applies `update` to the value under the lock, then wakes every
+    /// waiter.
+    pub fn notify_all<R>(&self, update: impl FnOnce(&mut T) -> R) -> R {
+        let (value, changed) = &*self.state;
+        let result = update(&mut value.lock_ignore_poison());
+        changed.notify_all();
+        result
+    }
+}
+
+impl<T> Clone for Monitor<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Monitor {
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<T: Default> Default for Monitor<T> {
+    #[inline]
+    fn default() -> Self {
+        Monitor::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Monitor<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Monitor").field(&self.state.0).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_waiter_blocks_until_condition_set() {
+        let ready = Monitor::new(false);
+        let waiter = {
+            let ready = ready.clone();
+            thread::spawn(move || *ready.wait_until(|ready| *ready))
+        };
+        thread::sleep(Duration::from_millis(10));
+        assert!(!waiter.is_finished());
+
+        ready.notify_one(|ready| *ready = true);
+        assert!(waiter.join().unwrap());
+    }
+
+    #[test]
+    fn test_wait_rechecks_after_unrelated_notifications() {
+        let stage = Monitor::new(0u32);
+        thread::scope(|s| {
+            let waiter = s.spawn(|| *stage.wait_until(|stage| *stage >= 5));
+            // Every step wakes the waiter, but only the last satisfies it.
+            for _ in 0..5 {
+                thread::sleep(Duration::from_millis(2));
+                stage.notify_all(|stage| *stage += 1);
+            }
+            assert_eq!(waiter.join().unwrap(), 5);
+        });
+    }
+
+    #[test]
+    fn test_condition_already_true_returns_immediately() {
+        let monitor = Monitor::new(vec![1]);
+        monitor.lock().push(2);
+        assert_eq!(monitor.wait_until(|items| items.len() == 2).len(), 2);
+        assert_eq!(monitor.notify_one(|items| items.pop()), Some(2));
+    }
+}
@@ -0,0 +1,280 @@
+//! An immutable, structurally shared stack (cons list) over `Arc`.
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+struct Node<T> {
+    value: T,
+    next: Option<Arc<Node<T>>>,
+}
+
+/// A persistent stack where every version shares its tail with the versions
+/// it was derived from.
+///
+/// [`push`](PersistentStack::push) and [`pop`](PersistentStack::pop) return new
+/// stacks in O(1) and never modify `self`, so older versions remain valid
+/// snapshots. Cloning is O(1), and stacks can be shared across threads when
+/// `T: Send + Sync`.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::PersistentStack;
+///
+///     let base = PersistentStack::new().push(1).push(2);
+///     let branch = base.push(3);
+///
+///     assert_eq!(base.iter().copied().collect::<Vec<_>>(), [2, 1]);
+///     assert_eq!(branch.iter().copied().collect::<Vec<_>>(), [3, 2, 1]);
+/// }
+/// ```
+pub struct PersistentStack<T> {
+    head: Option<Arc<Node<T>>>,
+    len: usize,
+}
+
+impl<T> PersistentStack<T> {
+    /// Creates an empty stack.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        PersistentStack { head: None, len: 0 }
+    }
+
+    /// Returns a new stack with `value` on top of `self`.
+    pub fn push(&self, value: T) -> Self {
+        PersistentStack {
+            head: Some(Arc::new(Node {
+                value,
+                next: self.head.clone(),
+            })),
+            len: self.len + 1,
+        }
+    }
+
+    /// Returns the top element and the stack beneath it, or `None` if empty.
+    pub fn pop(&self) -> Option<(&T, Self)> {
+        self.head.as_ref().map(|node| {
+            let rest = PersistentStack {
+                head: node.next.clone(),
+                len: self.len - 1,
+            };
+            (&node.value, rest)
+        })
+    }
+
+    /// Returns the top element, if any.
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    /// Returns the number of elements.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the stack has no elements.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Returns `true` if both stacks share the same top node.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Iterates from the top of the stack to the bottom.
+    #[inline]
+    pub fn iter(&self) -> PersistentStackIter<'_, T> {
+        PersistentStackIter {
+            next: self.head.as_deref(),
+        }
+    }
+}
+
+impl<T> Clone for PersistentStack<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        PersistentStack {
+            head: self.head.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Default for PersistentStack<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        PersistentStack::new()
+    }
+}
+
+impl<T> Drop for PersistentStack<T> {
+    fn drop(&mut self) {
+        // Unlink uniquely owned nodes iteratively so long stacks don't
+        // overflow the call stack with recursive drops.
+        let mut next = self.head.take();
+        while let Some(node) = next {
+            match Arc::try_unwrap(node) {
+                Ok(mut node) => next = node.next.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for PersistentStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for PersistentStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for PersistentStack<T> {}
+
+/// Builds a stack by pushing each item in turn, so the last item ends up on top.
+impl<T> FromIterator<T> for PersistentStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = PersistentStack::new();
+        for value in iter {
+            stack = stack.push(value);
+        }
+        stack
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentStack<T> {
+    type Item = &'a T;
+    type IntoIter = PersistentStackIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> PersistentStackIter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Iterator over a [`PersistentStack`], from top to bottom.
+pub struct PersistentStackIter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for PersistentStackIter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.value
+        })
+    }
+}
+
+/// Serializes as a sequence from top to bottom.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for PersistentStack<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for PersistentStack<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<T>::deserialize(deserializer).map(|items| items.into_iter().rev().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn items(stack: &PersistentStack<i32>) -> Vec<i32> {
+        stack.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_push_and_branch() {
+        let empty = PersistentStack::new();
+        let one = empty.push(1);
+        let two = one.push(2);
+        let branch_a = two.push(3);
+        let branch_b = two.push(4);
+
+        assert!(empty.is_empty());
+        assert_eq!(items(&two), [2, 1]);
+        assert_eq!(items(&branch_a), [3, 2, 1]);
+        assert_eq!(items(&branch_b), [4, 2, 1]);
+        assert_eq!(branch_a.len(), 3);
+
+        // Both branches share the untouched tail.
+        let (_, tail_a) = branch_a.pop().unwrap();
+        let (_, tail_b) = branch_b.pop().unwrap();
+        assert!(tail_a.ptr_eq(&tail_b));
+        assert!(tail_a.ptr_eq(&two));
+    }
+
+    #[test]
+    fn test_pop_does_not_modify() {
+        let stack: PersistentStack<i32> = (1..=3).collect();
+        let (top, rest) = stack.pop().unwrap();
+        assert_eq!(*top, 3);
+        assert_eq!(items(&rest), [2, 1]);
+        assert_eq!(items(&stack), [3, 2, 1]);
+        assert_eq!(stack.peek(), Some(&3));
+    }
+
+    #[test]
+    fn test_snapshots_across_threads() {
+        let base: PersistentStack<i32> = (0..10).collect();
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let base = base.clone();
+                thread::spawn(move || {
+                    let extended = base.push(100 + i);
+                    assert_eq!(extended.len(), 11);
+                    extended.iter().sum::<i32>()
+                })
+            })
+            .collect();
+
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.join().unwrap(), 45 + 100 + i as i32);
+        }
+        assert_eq!(base.len(), 10);
+    }
+
+    #[test]
+    fn test_long_stack_drop() {
+        let stack: PersistentStack<u32> = (0..200_000).collect();
+        assert_eq!(stack.len(), 200_000);
+        drop(stack);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let stack: PersistentStack<i32> = (1..=3).collect();
+        let json = serde_json::to_string(&stack).unwrap();
+        assert_eq!(json, "[3,2,1]");
+
+        let back: PersistentStack<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, stack);
+    }
+}
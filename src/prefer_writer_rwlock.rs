@@ -0,0 +1,302 @@
+//! A reader-writer lock that lets pending writers go first.
+
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+use crate::MutexExt;
+
+#[derive(Debug, Default)]
+struct State {
+    readers: usize,
+    writer: bool,
+    waiting_writers: usize,
+}
+
+/// A reader-writer lock biased toward writers.
+///
+/// Once a writer starts waiting, new readers queue behind it instead of
+/// joining the readers already inside, so the writer gets in as soon as
+/// those finish. Under continuous read traffic a std `RwLock` makes no such
+/// promise (its policy is platform-dependent), and a writer can wait
+/// indefinitely. The flip side is that a steady stream of writers can keep
+/// readers out.
+///
+/// The lock is built from a `Mutex` and two `Condvar`s and never poisons: a
+/// panic while a guard is held simply releases the lock as the guard drops.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::WrapExt;
+///
+///     let config = vec!["a"].arc_prefer_writer_rwlock();
+///     assert_eq!(config.read().len(), 1);
+///     config.write().push("b");
+///     assert_eq!(*config.read(), ["a", "b"]);
+/// }
+/// ```
+pub struct PreferWriterRwLock<T: ?Sized> {
+    state: Mutex<State>,
+    readers_cv: Condvar,
+    writers_cv: Condvar,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: the state machine hands out either one `&mut T` or any number of
+// `&T`, never both, so the usual `RwLock` bounds apply.
+unsafe impl<T: ?Sized + Send> Send for PreferWriterRwLock<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for PreferWriterRwLock<T> {}
+
+impl<T> PreferWriterRwLock<T> {
+    /// Creates an unlocked lock.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        PreferWriterRwLock {
+            state: Mutex::new(State::default()),
+            readers_cv: Condvar::new(),
+            writers_cv: Condvar::new(),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Consumes the lock, returning the value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: ?Sized> PreferWriterRwLock<T> {
+    /// Acquires shared access, waiting while a writer holds the lock or is
+    /// waiting for it.
+    pub fn read(&self) -> PreferWriterReadGuard<'_, T> {
+        let state = self.state.lock_ignore_poison();
+        let mut state = self
+            .readers_cv
+            .wait_while(state, |s| s.writer || s.waiting_writers > 0)
+            .unwrap_or_else(PoisonError::into_inner);
+        state.readers += 1;
+        PreferWriterReadGuard { lock: self }
+    }
+
+    /// Acquires shared access if that is possible without waiting.
+    pub fn try_read(&self) -> Option<PreferWriterReadGuard<'_, T>> {
+        let mut state = self.state.lock_ignore_poison();
+        if state.writer || state.waiting_writers > 0 {
+            return None;
+        }
+        state.readers += 1;
+        Some(PreferWriterReadGuard { lock: self })
+    }
+
+    /// Acquires exclusive access. From the moment this starts waiting, no new
+    /// readers are admitted.
+    pub fn write(&self) -> PreferWriterWriteGuard<'_, T> {
+        let mut state = self.state.lock_ignore_poison();
+        state.waiting_writers += 1;
+        let mut state = self
+            .writers_cv
+            .wait_while(state, |s| s.writer || s.readers > 0)
+            .unwrap_or_else(PoisonError::into_inner);
+        state.waiting_writers -= 1;
+        state.writer = true;
+        PreferWriterWriteGuard { lock: self }
+    }
+
+    /// Acquires exclusive access if the lock is free.
+    pub fn try_write(&self) -> Option<PreferWriterWriteGuard<'_, T>> {
+        let mut state = self.state.lock_ignore_poison();
+        if state.writer || state.readers > 0 {
+            return None;
+        }
+        state.writer = true;
+        Some(PreferWriterWriteGuard { lock: self })
+    }
+
+    /// Returns a mutable reference to the value; no locking is needed since
+    /// the borrow is exclusive.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock_ignore_poison()
+    }
+}
+
+impl<T: Default> Default for PreferWriterRwLock<T> {
+    #[inline]
+    fn default() -> Self {
+        PreferWriterRwLock::new(T::default())
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for PreferWriterRwLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("PreferWriterRwLock");
+        match self.try_read() {
+            Some(guard) => d.field("value", &&*guard),
+            None => d.field("value", &format_args!("<locked>")),
+        };
+        d.finish()
+    }
+}
+
+/// Shared guard returned by [`PreferWriterRwLock::read`].
+pub struct PreferWriterReadGuard<'a, T: ?Sized> {
+    lock: &'a PreferWriterRwLock<T>,
+}
+
+impl<T: ?Sized> Deref for PreferWriterReadGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // SAFETY: a reader is registered, so no writer can be active.
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for PreferWriterReadGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.lock.state();
+        state.readers -= 1;
+        if state.readers == 0 && state.waiting_writers > 0 {
+            drop(state);
+            self.lock.writers_cv.notify_one();
+        }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for PreferWriterReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// Exclusive guard returned by [`PreferWriterRwLock::write`].
+pub struct PreferWriterWriteGuard<'a, T: ?Sized> {
+    lock: &'a PreferWriterRwLock<T>,
+}
+
+impl<T: ?Sized> Deref for PreferWriterWriteGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // SAFETY: the writer flag is set, so this guard has exclusive access.
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for PreferWriterWriteGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: as for `deref`.
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for PreferWriterWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.lock.state();
+        state.writer = false;
+        let writers_waiting = state.waiting_writers > 0;
+        drop(state);
+        if writers_waiting {
+            self.lock.writers_cv.notify_one();
+        } else {
+            self.lock.readers_cv.notify_all();
+        }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for PreferWriterWriteGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_writer_acquires_under_read_traffic() {
+        let lock = Arc::new(PreferWriterRwLock::new(0u64));
+        let stop = Arc::new(AtomicBool::new(false));
+        let reads = Arc::new(AtomicUsize::new(0));
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let (lock, stop, reads) = (Arc::clone(&lock), Arc::clone(&stop), Arc::clone(&reads));
+                thread::spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        let guard = lock.read();
+                        reads.fetch_add(1, Ordering::Relaxed);
+                        // Overlapping reads keep the lock continuously read-held.
+                        thread::sleep(Duration::from_micros(200));
+                        drop(guard);
+                    }
+                })
+            })
+            .collect();
+
+        while reads.load(Ordering::Relaxed) < 20 {
+            thread::yield_now();
+        }
+        let started = Instant::now();
+        *lock.write() = 1;
+        let waited = started.elapsed();
+
+        stop.store(true, Ordering::Relaxed);
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        assert_eq!(*lock.read(), 1);
+        assert!(waited < Duration::from_secs(5), "writer waited {:?}", waited);
+    }
+
+    #[test]
+    fn test_waiting_writer_blocks_new_readers() {
+        let lock = Arc::new(PreferWriterRwLock::new(Vec::<u32>::new()));
+        let first = lock.read();
+
+        let writer = {
+            let lock = Arc::clone(&lock);
+            thread::spawn(move || lock.write().push(1))
+        };
+        while lock.state().waiting_writers == 0 {
+            thread::yield_now();
+        }
+        assert!(lock.try_read().is_none());
+
+        drop(first);
+        writer.join().unwrap();
+        assert_eq!(*lock.try_read().unwrap(), [1]);
+    }
+
+    #[test]
+    fn test_panic_in_writer_releases_lock() {
+        let lock = Arc::new(PreferWriterRwLock::new(1u8));
+        let cloned = Arc::clone(&lock);
+        let result = thread::spawn(move || {
+            let _guard = cloned.write();
+            panic!("writer failed");
+        })
+        .join();
+
+        assert!(result.is_err());
+        assert!(lock.try_write().is_some());
+        assert_eq!(format!("{:?}", lock), "PreferWriterRwLock { value: 1 }");
+    }
+}
@@ -0,0 +1,23 @@
+//! Atomic and lock types used inside the crate's own primitives.
+//!
+//! With the `loom` feature these are `loom`'s model-checked equivalents, so
+//! the primitives built on them can be exercised under `loom::model`.
+//! Otherwise they are the real `std` types, with `AtomicU64` taken from
+//! `wide_atomic` so it follows the `portable-atomic` feature. `Ordering` is
+//! shared by both.
+//!
+//! `loom` types panic when used outside a model, so the feature is meant for
+//! test builds only; the regular tests of the affected primitives are
+//! compiled out while it is enabled.
+
+#[cfg(not(feature = "loom"))]
+pub(crate) use std::sync::atomic::{AtomicU16, AtomicU32, AtomicU8};
+#[cfg(all(not(feature = "loom"), any(target_has_atomic = "64", feature = "portable-atomic")))]
+pub(crate) use crate::wide_atomic::AtomicU64;
+#[cfg(not(feature = "loom"))]
+pub(crate) use std::sync::Mutex;
+
+#[cfg(feature = "loom")]
+pub(crate) use loom::sync::atomic::{AtomicU16, AtomicU32, AtomicU64, AtomicU8};
+#[cfg(feature = "loom")]
+pub(crate) use loom::sync::Mutex;
@@ -0,0 +1,116 @@
+//! A `Cell`-backed flag that only ever escalates until reset.
+
+use core::cell::Cell;
+
+/// Tracks the highest level raised since the last reset.
+///
+/// Useful for escalation such as "the worst log level seen during this
+/// request": raising a lower level than the current one has no effect.
+///
+/// # Example
+///
+/// ```
+/// use autowrap::PriorityFlag;
+///
+/// let flag = PriorityFlag::new();
+/// flag.raise(2);
+/// flag.raise(1);
+/// assert_eq!(flag.level(), 2);
+/// assert_eq!(flag.reset(), 2);
+/// assert_eq!(flag.level(), 0);
+/// ```
+#[derive(Debug, Default)]
+pub struct PriorityFlag {
+    level: Cell<u8>,
+}
+
+impl PriorityFlag {
+    /// Creates a flag at level `0`.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        PriorityFlag { level: Cell::new(0) }
+    }
+
+    /// Raises the flag to `level` if it is higher than the current level,
+    /// returning the resulting maximum.
+    #[inline]
+    pub fn raise(&self, level: u8) -> u8 {
+        let max = self.level.get().max(level);
+        self.level.set(max);
+        max
+    }
+
+    /// Returns the highest level raised since the last reset.
+    #[inline(always)]
+    pub fn level(&self) -> u8 {
+        self.level.get()
+    }
+
+    /// Returns `true` if any non-zero level has been raised.
+    #[inline(always)]
+    pub fn is_raised(&self) -> bool {
+        self.level.get() != 0
+    }
+
+    /// Clears the flag to `0`, returning the maximum it held.
+    #[inline(always)]
+    pub fn reset(&self) -> u8 {
+        self.level.replace(0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PriorityFlag {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.level())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PriorityFlag {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let flag = PriorityFlag::new();
+        flag.raise(u8::deserialize(deserializer)?);
+        Ok(flag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highest_wins() {
+        let flag = PriorityFlag::new();
+        assert!(!flag.is_raised());
+
+        assert_eq!(flag.raise(3), 3);
+        assert_eq!(flag.raise(1), 3);
+        assert_eq!(flag.raise(5), 5);
+        assert_eq!(flag.raise(4), 5);
+        assert_eq!(flag.level(), 5);
+        assert!(flag.is_raised());
+    }
+
+    #[test]
+    fn test_reset() {
+        let flag = PriorityFlag::new();
+        flag.raise(7);
+        assert_eq!(flag.reset(), 7);
+        assert_eq!(flag.reset(), 0);
+
+        flag.raise(2);
+        assert_eq!(flag.level(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let flag = PriorityFlag::new();
+        flag.raise(4);
+        assert_eq!(serde_json::to_string(&flag).unwrap(), "4");
+
+        let back: PriorityFlag = serde_json::from_str("4").unwrap();
+        assert_eq!(back.level(), 4);
+    }
+}
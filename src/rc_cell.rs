@@ -0,0 +1,206 @@
+//! A cloneable handle to an `Rc<RefCell<T>>`.
+
+use std::cell::{Ref, RefCell, RefMut};
+use std::fmt;
+use std::rc::Rc;
+
+/// Shared, single-threaded, mutable state: an `Rc<RefCell<T>>` with the
+/// borrow methods one level up.
+///
+/// Cloning an `RcCell` clones only the `Rc`, so every clone refers to the
+/// same value and `T` need not be `Clone`. The `RefCell` borrow rules still
+/// apply across all clones: any number of [`borrow`](RcCell::borrow)s, or
+/// one [`borrow_mut`](RcCell::borrow_mut), at a time. Prefer the closure
+/// forms [`with`](RcCell::with) and [`with_mut`](RcCell::with_mut), which
+/// can't accidentally hold a borrow past the statement that needs it.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::WrapExt;
+///
+///     let children = Vec::<&str>::new().rc_cell_shared();
+///     let builder = children.clone();
+///
+///     builder.with_mut(|c| c.push("leaf"));
+///     assert_eq!(children.with(|c| c.len()), 1);
+///     assert_eq!(*children.borrow(), ["leaf"]);
+/// }
+/// ```
+pub struct RcCell<T: ?Sized> {
+    inner: Rc<RefCell<T>>,
+}
+
+impl<T> RcCell<T> {
+    /// Creates a new shared cell.
+    #[inline(always)]
+    pub fn new(value: T) -> Self {
+        RcCell {
+            inner: Rc::new(RefCell::new(value)),
+        }
+    }
+}
+
+impl<T: ?Sized> RcCell<T> {
+    /// Immutably borrows the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is mutably borrowed through any clone.
+    #[inline(always)]
+    #[track_caller]
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.inner.borrow()
+    }
+
+    /// Mutably borrows the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is borrowed at all through any clone.
+    #[inline(always)]
+    #[track_caller]
+    pub fn borrow_mut(&self) -> RefMut<'_, T> {
+        self.inner.borrow_mut()
+    }
+
+    /// Runs `f` with a shared borrow of the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is mutably borrowed through any clone.
+    #[inline]
+    #[track_caller]
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.inner.borrow())
+    }
+
+    /// Runs `f` with a mutable borrow of the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is borrowed at all through any clone.
+    #[inline]
+    #[track_caller]
+    pub fn with_mut<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.inner.borrow_mut())
+    }
+
+    /// Returns `true` if both handles refer to the same value.
+    #[inline(always)]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Rc::ptr_eq(&this.inner, &other.inner)
+    }
+
+    /// Returns the underlying `Rc<RefCell<T>>`.
+    #[inline(always)]
+    pub fn into_rc(self) -> Rc<RefCell<T>> {
+        self.inner
+    }
+}
+
+impl<T> RcCell<T> {
+    /// Moves the value into an `Arc<Mutex<T>>` (the
+    /// [`IntoShared`](crate::IntoShared) form), for code moving from one
+    /// thread to several.
+    ///
+    /// This only succeeds if this is the last handle to the value: other
+    /// `RcCell` clones (or the `Rc` they came from) would otherwise keep
+    /// pointing at the old cell and silently stop seeing updates. If the
+    /// value is still shared, `self` is returned unchanged in `Err`. A
+    /// borrow can't be outstanding, since it would borrow `self`.
+    #[cfg(feature = "sync")]
+    pub fn into_arc_mutex(self) -> Result<std::sync::Arc<std::sync::Mutex<T>>, Self> {
+        match Rc::try_unwrap(self.inner) {
+            Ok(cell) => Ok(std::sync::Arc::new(std::sync::Mutex::new(cell.into_inner()))),
+            Err(inner) => Err(RcCell { inner }),
+        }
+    }
+}
+
+impl<T: ?Sized> Clone for RcCell<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        RcCell {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: Default> Default for RcCell<T> {
+    #[inline]
+    fn default() -> Self {
+        RcCell::new(T::default())
+    }
+}
+
+impl<T: ?Sized> From<Rc<RefCell<T>>> for RcCell<T> {
+    #[inline(always)]
+    fn from(inner: Rc<RefCell<T>>) -> Self {
+        RcCell { inner }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for RcCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inner.try_borrow() {
+            Ok(value) => f.debug_tuple("RcCell").field(&&*value).finish(),
+            Err(_) => f.debug_tuple("RcCell").field(&format_args!("<borrowed>")).finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_value() {
+        struct NotClone(u32);
+
+        let a = RcCell::new(NotClone(1));
+        let b = a.clone();
+        assert!(RcCell::ptr_eq(&a, &b));
+
+        b.borrow_mut().0 += 1;
+        a.with_mut(|v| v.0 *= 10);
+        assert_eq!(b.with(|v| v.0), 20);
+        assert_eq!(Rc::strong_count(&a.into_rc()), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn test_double_mutable_borrow_panics() {
+        let a = RcCell::new(vec![1]);
+        let b = a.clone();
+        let _first = a.borrow_mut();
+        // A second mutable borrow, even through another clone, panics.
+        let _second = b.borrow_mut();
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_into_arc_mutex_requires_unique_owner() {
+        let cell = RcCell::new(vec![1u8]);
+        let other = cell.clone();
+
+        let cell = cell.into_arc_mutex().unwrap_err();
+        assert!(RcCell::ptr_eq(&cell, &other));
+        cell.borrow_mut().push(2);
+        assert_eq!(*other.borrow(), [1, 2]);
+
+        drop(other);
+        let shared = cell.into_arc_mutex().unwrap();
+        assert_eq!(*shared.lock().unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn test_debug_while_borrowed() {
+        let cell = RcCell::new(3u8);
+        assert_eq!(format!("{:?}", cell), "RcCell(3)");
+        let _guard = cell.borrow_mut();
+        assert_eq!(format!("{:?}", cell), "RcCell(<borrowed>)");
+    }
+}
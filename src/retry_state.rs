@@ -0,0 +1,113 @@
+//! `Cell`-backed exponential backoff state for single-threaded retry loops.
+
+use core::cell::Cell;
+use core::time::Duration;
+
+#[inline(always)]
+fn to_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// Tracks the attempt count and the next exponential backoff delay.
+///
+/// Each call to [`next_delay`](RetryState::next_delay) returns the current
+/// delay and doubles it for the following attempt, never exceeding the
+/// configured cap. Everything is stored in `Cell`s, so the state can be shared
+/// by reference inside a retry loop.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+/// use autowrap::RetryState;
+///
+/// let retry = RetryState::new(Duration::from_millis(100), Duration::from_millis(300));
+/// assert_eq!(retry.next_delay(), Duration::from_millis(100));
+/// assert_eq!(retry.next_delay(), Duration::from_millis(200));
+/// assert_eq!(retry.next_delay(), Duration::from_millis(300));
+/// assert_eq!(retry.attempts(), 3);
+/// ```
+#[derive(Debug)]
+pub struct RetryState {
+    attempts: Cell<u32>,
+    next_nanos: Cell<u64>,
+    initial_nanos: u64,
+    max_nanos: u64,
+}
+
+impl RetryState {
+    /// Creates a retry state starting at `initial` and capped at `max`.
+    ///
+    /// Durations beyond `u64::MAX` nanoseconds (about 584 years) saturate.
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        let max_nanos = to_nanos(max);
+        let initial_nanos = to_nanos(initial).min(max_nanos);
+        RetryState {
+            attempts: Cell::new(0),
+            next_nanos: Cell::new(initial_nanos),
+            initial_nanos,
+            max_nanos,
+        }
+    }
+
+    /// Records an attempt and returns how long to wait before the next one.
+    pub fn next_delay(&self) -> Duration {
+        let current = self.next_nanos.get();
+        self.next_nanos
+            .set(current.saturating_mul(2).min(self.max_nanos));
+        self.attempts.set(self.attempts.get().saturating_add(1));
+        Duration::from_nanos(current)
+    }
+
+    /// Returns the delay the next call to [`next_delay`](RetryState::next_delay)
+    /// will hand out, without recording an attempt.
+    #[inline(always)]
+    pub fn peek_delay(&self) -> Duration {
+        Duration::from_nanos(self.next_nanos.get())
+    }
+
+    /// Returns the number of attempts recorded since the last reset.
+    #[inline(always)]
+    pub fn attempts(&self) -> u32 {
+        self.attempts.get()
+    }
+
+    /// Clears the attempt count and restores the initial delay.
+    #[inline]
+    pub fn reset(&self) {
+        self.attempts.set(0);
+        self.next_nanos.set(self.initial_nanos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doubles_up_to_cap() {
+        let retry = RetryState::new(Duration::from_millis(10), Duration::from_millis(100));
+        let delays: [u64; 6] = core::array::from_fn(|_| retry.next_delay().as_millis() as u64);
+        assert_eq!(delays, [10, 20, 40, 80, 100, 100]);
+        assert_eq!(retry.attempts(), 6);
+    }
+
+    #[test]
+    fn test_reset() {
+        let retry = RetryState::new(Duration::from_secs(1), Duration::from_secs(60));
+        retry.next_delay();
+        retry.next_delay();
+        assert_eq!(retry.peek_delay(), Duration::from_secs(4));
+
+        retry.reset();
+        assert_eq!(retry.attempts(), 0);
+        assert_eq!(retry.next_delay(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_saturates() {
+        let retry = RetryState::new(Duration::MAX, Duration::MAX);
+        assert_eq!(retry.next_delay(), Duration::from_nanos(u64::MAX));
+        assert_eq!(retry.next_delay(), Duration::from_nanos(u64::MAX));
+    }
+}
@@ -0,0 +1,163 @@
+//! A bounded counting semaphore over `Mutex` and `Condvar`.
+
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+
+use crate::MutexExt;
+
+/// A counting semaphore that never holds more than its initial permits.
+///
+/// [`acquire`](Semaphore::acquire) takes a permit, blocking while none are
+/// available, and [`release`](Semaphore::release) returns one, waking a
+/// waiter. Clones share the same permits, so a clone can be moved into each
+/// worker thread.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::Semaphore;
+///
+///     let permits = Semaphore::new(2);
+///     permits.acquire();
+///     assert!(permits.try_acquire());
+///     assert!(!permits.try_acquire());
+///
+///     permits.release();
+///     assert_eq!(permits.available(), 1);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Semaphore {
+    state: Arc<(Mutex<usize>, Condvar)>,
+    max: usize,
+}
+
+impl Semaphore {
+    /// Creates a semaphore with `permits` permits, all available.
+    #[inline]
+    pub fn new(permits: usize) -> Self {
+        Semaphore {
+            state: Arc::new((Mutex::new(permits), Condvar::new())),
+            max: permits,
+        }
+    }
+
+    /// Takes a permit, blocking until one is available.
+    pub fn acquire(&self) {
+        let (count, available) = &*self.state;
+        let mut count = available
+            .wait_while(count.lock_ignore_poison(), |count| *count == 0)
+            .unwrap_or_else(PoisonError::into_inner);
+        *count -= 1;
+    }
+
+    /// Takes a permit if one is available, without blocking.
+    pub fn try_acquire(&self) -> bool {
+        let mut count = self.state.0.lock_ignore_poison();
+        if *count == 0 {
+            return false;
+        }
+        *count -= 1;
+        true
+    }
+
+    /// Returns a permit, waking one blocked [`acquire`](Semaphore::acquire).
+    ///
+    /// # Panics
+    ///
+    /// Panics if every permit is already available, since that means a
+    /// permit was released without being acquired.
+    pub fn release(&self) {
+        let (count, available) = &*self.state;
+        let mut count = count.lock_ignore_poison();
+        assert!(*count < self.max, "semaphore released more often than acquired");
+        *count += 1;
+        drop(count);
+        available.notify_one();
+    }
+
+    /// Returns the number of permits currently available.
+    #[inline]
+    pub fn available(&self) -> usize {
+        *self.state.0.lock_ignore_poison()
+    }
+
+    /// Returns the total number of permits.
+    #[inline(always)]
+    pub fn permits(&self) -> usize {
+        self.max
+    }
+}
+
+impl fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("available", &self.available())
+            .field("permits", &self.max)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_at_most_n_concurrent() {
+        const PERMITS: usize = 3;
+
+        let semaphore = Semaphore::new(PERMITS);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..10)
+            .map(|_| {
+                let (semaphore, running, peak) =
+                    (semaphore.clone(), Arc::clone(&running), Arc::clone(&peak));
+                thread::spawn(move || {
+                    semaphore.acquire();
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(5));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    semaphore.release();
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(peak.load(Ordering::SeqCst) <= PERMITS);
+        assert_eq!(semaphore.available(), PERMITS);
+    }
+
+    #[test]
+    fn test_release_wakes_waiter() {
+        let semaphore = Semaphore::new(1);
+        semaphore.acquire();
+
+        let waiter = semaphore.clone();
+        let handle = thread::spawn(move || {
+            waiter.acquire();
+            waiter.release();
+        });
+        thread::sleep(Duration::from_millis(10));
+        assert!(!handle.is_finished());
+
+        semaphore.release();
+        handle.join().unwrap();
+        assert_eq!(semaphore.available(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "released more often than acquired")]
+    fn test_release_beyond_permits_panics() {
+        Semaphore::new(1).release();
+    }
+}
@@ -0,0 +1,254 @@
+//! A shared `parking_lot` mutex with an optional debug name and lock timeout.
+
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::{Mutex, MutexGuard};
+
+/// An `Arc<parking_lot::Mutex<T>>` configured through [`SharedMut::builder`].
+///
+/// A debug name shows up in `Debug` output and in [`LockTimeout`] errors,
+/// which makes it easy to tell which of many locks a stuck thread was waiting
+/// on. With a timeout configured, [`lock`](SharedMut::lock) gives up after
+/// that long instead of blocking forever.
+///
+/// Clones share the mutex and the configuration.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "parking_lot")]
+/// {
+///     use autowrap::SharedMut;
+///     use std::time::Duration;
+///
+///     let cache = SharedMut::builder()
+///         .name("cache")
+///         .timeout(Duration::from_millis(50))
+///         .build(Vec::<u32>::new());
+///
+///     cache.lock().unwrap().push(1);
+///
+///     let held = cache.lock().unwrap();
+///     let err = cache.lock().unwrap_err();
+///     assert_eq!(err.name(), Some("cache"));
+///     drop(held);
+/// }
+/// ```
+pub struct SharedMut<T> {
+    inner: Arc<Mutex<T>>,
+    name: Option<Arc<str>>,
+    timeout: Option<Duration>,
+}
+
+impl<T> SharedMut<T> {
+    /// Creates an unnamed shared value whose `lock` blocks indefinitely.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        SharedMut::builder().build(value)
+    }
+
+    /// Acquires the lock.
+    ///
+    /// Without a timeout this blocks until the lock is free and never fails.
+    /// With one, it returns [`LockTimeout`] once the timeout elapses.
+    pub fn lock(&self) -> Result<MutexGuard<'_, T>, LockTimeout> {
+        match self.timeout {
+            None => Ok(self.inner.lock()),
+            Some(timeout) => self.inner.try_lock_for(timeout).ok_or_else(|| LockTimeout {
+                name: self.name.clone(),
+                timeout,
+            }),
+        }
+    }
+
+    /// Attempts to acquire the lock without blocking.
+    #[inline]
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        self.inner.try_lock()
+    }
+
+    /// Returns the debug name, if one was configured.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the lock timeout, if one was configured.
+    #[inline(always)]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+impl SharedMut<()> {
+    /// Returns a builder for configuring the name and timeout; the value type
+    /// is chosen by [`SharedMutBuilder::build`].
+    #[inline(always)]
+    pub fn builder() -> SharedMutBuilder {
+        SharedMutBuilder::default()
+    }
+}
+
+impl<T> Clone for SharedMut<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        SharedMut {
+            inner: Arc::clone(&self.inner),
+            name: self.name.clone(),
+            timeout: self.timeout,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SharedMut<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("SharedMut");
+        if let Some(name) = &self.name {
+            d.field("name", name);
+        }
+        match self.inner.try_lock() {
+            Some(guard) => d.field("value", &&*guard),
+            None => d.field("value", &format_args!("<locked>")),
+        };
+        d.finish()
+    }
+}
+
+/// Builder returned by [`SharedMut::builder`].
+#[derive(Clone, Debug, Default)]
+pub struct SharedMutBuilder {
+    name: Option<Arc<str>>,
+    timeout: Option<Duration>,
+}
+
+impl SharedMutBuilder {
+    /// Sets the debug name reported by `Debug` and [`LockTimeout`].
+    #[inline]
+    pub fn name(mut self, name: impl Into<Arc<str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Makes [`SharedMut::lock`] give up after `timeout`.
+    #[inline(always)]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Creates the shared value.
+    #[inline]
+    pub fn build<T>(self, value: T) -> SharedMut<T> {
+        SharedMut {
+            inner: Arc::new(Mutex::new(value)),
+            name: self.name,
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// Error returned by [`SharedMut::lock`] when the configured timeout elapses.
+#[derive(Clone, Debug)]
+pub struct LockTimeout {
+    name: Option<Arc<str>>,
+    timeout: Duration,
+}
+
+impl LockTimeout {
+    /// Returns the name of the lock that timed out, if it had one.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns how long the lock was waited for.
+    #[inline(always)]
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl fmt::Display for LockTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(
+                f,
+                "timed out after {:?} waiting for lock `{}`",
+                self.timeout, name
+            ),
+            None => write!(f, "timed out after {:?} waiting for lock", self.timeout),
+        }
+    }
+}
+
+impl Error for LockTimeout {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_builder_configures_name() {
+        let shared = SharedMut::builder().name("config").build(1u32);
+        assert_eq!(shared.name(), Some("config"));
+        assert_eq!(shared.timeout(), None);
+
+        *shared.lock().unwrap() += 1;
+        assert_eq!(*shared.lock().unwrap(), 2);
+        assert_eq!(format!("{:?}", shared), r#"SharedMut { name: "config", value: 2 }"#);
+    }
+
+    #[test]
+    fn test_timeout_reports_name() {
+        let shared = SharedMut::builder()
+            .name(String::from("jobs"))
+            .timeout(Duration::from_millis(20))
+            .build(Vec::<u32>::new());
+
+        let holder = shared.clone();
+        let guard = holder.lock().unwrap();
+        let err = thread::scope(|s| s.spawn(|| shared.lock().map(|_| ())).join().unwrap())
+            .unwrap_err();
+        assert_eq!(err.name(), Some("jobs"));
+        assert_eq!(err.timeout(), Duration::from_millis(20));
+        assert_eq!(err.to_string(), "timed out after 20ms waiting for lock `jobs`");
+
+        drop(guard);
+        shared.lock().unwrap().push(1);
+        assert_eq!(*holder.lock().unwrap(), [1]);
+    }
+
+    #[test]
+    fn test_unconfigured_lock_blocks() {
+        let shared = SharedMut::new(0u32);
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = shared.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        *shared.lock().unwrap() += 1;
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*shared.lock().unwrap(), 400);
+    }
+
+    #[test]
+    fn test_unnamed_timeout_message() {
+        let shared = SharedMut::builder()
+            .timeout(Duration::from_millis(1))
+            .build(());
+        let _guard = shared.try_lock().unwrap();
+        let err = shared.lock().unwrap_err();
+        assert_eq!(err.to_string(), "timed out after 1ms waiting for lock");
+    }
+}
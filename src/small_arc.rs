@@ -0,0 +1,136 @@
+//! A cheaply clonable handle that skips the allocation for word-sized values.
+
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A clonable, shareable handle to a `Copy` value, stored inline when `T`
+/// fits in a pointer and in an `Arc<T>` otherwise.
+///
+/// Cloning an inline `SmallArc` copies the value; cloning a heap one bumps
+/// the refcount. Either way the value is immutable, so the two are
+/// indistinguishable through the API: [`get`](SmallArc::get) returns a copy
+/// and `Deref` a reference. The choice is made from `size_of::<T>()` when
+/// the handle is created and is visible only through
+/// [`is_inline`](SmallArc::is_inline).
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::WrapExt;
+///
+///     let id = 7u32.small_arc();
+///     let table = [0u8; 64].small_arc();
+///     assert!(id.is_inline() && !table.is_inline());
+///
+///     let handles = (id.clone(), table.clone());
+///     assert_eq!(handles.0.get(), 7);
+///     assert_eq!(handles.1.len(), 64);
+/// }
+/// ```
+pub struct SmallArc<T> {
+    repr: Repr<T>,
+}
+
+enum Repr<T> {
+    Inline(T),
+    Heap(Arc<T>),
+}
+
+impl<T: Copy> SmallArc<T> {
+    /// Whether values of type `T` are stored inline.
+    pub const INLINE: bool = core::mem::size_of::<T>() <= core::mem::size_of::<usize>();
+
+    /// Creates a handle, allocating only if `T` is larger than a pointer.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        let repr = if Self::INLINE {
+            Repr::Inline(value)
+        } else {
+            Repr::Heap(Arc::new(value))
+        };
+        SmallArc { repr }
+    }
+
+    /// Returns a copy of the value.
+    #[inline(always)]
+    pub fn get(&self) -> T {
+        **self
+    }
+
+    /// Returns `true` if the value is stored inline rather than in an `Arc`.
+    #[inline(always)]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.repr, Repr::Inline(_))
+    }
+}
+
+impl<T> Deref for SmallArc<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        match &self.repr {
+            Repr::Inline(value) => value,
+            Repr::Heap(arc) => arc,
+        }
+    }
+}
+
+impl<T: Copy> Clone for SmallArc<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        let repr = match &self.repr {
+            Repr::Inline(value) => Repr::Inline(*value),
+            Repr::Heap(arc) => Repr::Heap(Arc::clone(arc)),
+        };
+        SmallArc { repr }
+    }
+}
+
+impl<T: Copy + Default> Default for SmallArc<T> {
+    #[inline]
+    fn default() -> Self {
+        SmallArc::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SmallArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SmallArc").field(&**self).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_sized_value_is_inline() {
+        let a = SmallArc::new(42u32);
+        let b = a.clone();
+        assert!(a.is_inline() && b.is_inline());
+        assert_eq!((a.get(), *b), (42, 42));
+        assert_eq!(format!("{:?}", a), "SmallArc(42)");
+    }
+
+    #[test]
+    fn test_large_value_shares_allocation() {
+        let mut bytes = [0u8; 64];
+        bytes[63] = 9;
+        let a = SmallArc::new(bytes);
+        let b = a.clone();
+
+        assert!(!a.is_inline());
+        match (&a.repr, &b.repr) {
+            (Repr::Heap(x), Repr::Heap(y)) => {
+                assert!(Arc::ptr_eq(x, y));
+                assert_eq!(Arc::strong_count(x), 2);
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(b.get()[63], 9);
+    }
+}
@@ -0,0 +1,260 @@
+//! A wait-free single-producer single-consumer ring buffer.
+
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A bounded ring buffer for exactly one producer and one consumer thread.
+///
+/// The buffer is created with [`new`](SpscRing::new) and then
+/// [`split`](SpscRing::split) into an [`SpscProducer`] and an [`SpscConsumer`].
+/// Each half is `Send` but not `Clone`, so the single-producer and
+/// single-consumer requirement is enforced by ownership. `push` and `pop`
+/// complete in a bounded number of steps: each side only reads the other
+/// side's index and publishes its own with a release store.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::SpscRing;
+///
+///     let (mut tx, mut rx) = SpscRing::new(2).split();
+///     assert!(tx.push(1).is_ok());
+///     assert!(tx.push(2).is_ok());
+///     assert_eq!(tx.push(3), Err(3));
+///
+///     assert_eq!(rx.pop(), Some(1));
+///     assert_eq!(rx.pop(), Some(2));
+///     assert_eq!(rx.pop(), None);
+/// }
+/// ```
+pub struct SpscRing<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    // Both indices increase monotonically (wrapping) and are reduced modulo
+    // the capacity only when addressing a slot.
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+// SAFETY: a slot is only ever accessed by the producer before it publishes
+// `tail`, or by the consumer before it publishes `head`, so values only need
+// to be sendable between the two threads.
+unsafe impl<T: Send> Send for SpscRing<T> {}
+unsafe impl<T: Send> Sync for SpscRing<T> {}
+
+impl<T> SpscRing<T> {
+    /// Creates an empty ring holding up to `capacity` items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "SpscRing capacity must be non-zero");
+        SpscRing {
+            slots: (0..capacity)
+                .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+                .collect(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Splits the ring into its producer and consumer halves.
+    pub fn split(self) -> (SpscProducer<T>, SpscConsumer<T>) {
+        let ring = Arc::new(self);
+        (
+            SpscProducer {
+                ring: Arc::clone(&ring),
+            },
+            SpscConsumer { ring },
+        )
+    }
+
+    /// Returns the maximum number of items the ring can hold.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of items currently queued.
+    ///
+    /// When called from a third thread the result may already be stale.
+    #[inline]
+    pub fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        tail.wrapping_sub(head)
+    }
+
+    /// Returns `true` if no items are queued.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline(always)]
+    fn slot(&self, index: usize) -> *mut MaybeUninit<T> {
+        self.slots[index % self.slots.len()].get()
+    }
+}
+
+impl<T> Drop for SpscRing<T> {
+    fn drop(&mut self) {
+        let head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+        let mut index = head;
+        while index != tail {
+            // SAFETY: slots in `head..tail` were written and not yet consumed,
+            // and `&mut self` guarantees neither half is still alive.
+            unsafe { (*self.slot(index)).assume_init_drop() };
+            index = index.wrapping_add(1);
+        }
+    }
+}
+
+impl<T> fmt::Debug for SpscRing<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpscRing")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+/// The sending half of an [`SpscRing`].
+pub struct SpscProducer<T> {
+    ring: Arc<SpscRing<T>>,
+}
+
+impl<T> SpscProducer<T> {
+    /// Enqueues `value`, handing it back if the ring is full.
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        let tail = self.ring.tail.load(Ordering::Relaxed);
+        let head = self.ring.head.load(Ordering::Acquire);
+        if tail.wrapping_sub(head) == self.ring.capacity() {
+            return Err(value);
+        }
+        // SAFETY: the slot at `tail` is outside `head..tail`, so the consumer
+        // won't touch it until the release store below publishes it.
+        unsafe { (*self.ring.slot(tail)).write(value) };
+        self.ring.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    /// Returns the shared ring, e.g. to query its length.
+    #[inline(always)]
+    pub fn ring(&self) -> &SpscRing<T> {
+        &self.ring
+    }
+}
+
+impl<T> fmt::Debug for SpscProducer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SpscProducer").field(&*self.ring).finish()
+    }
+}
+
+/// The receiving half of an [`SpscRing`].
+pub struct SpscConsumer<T> {
+    ring: Arc<SpscRing<T>>,
+}
+
+impl<T> SpscConsumer<T> {
+    /// Dequeues the oldest item, or returns `None` if the ring is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let head = self.ring.head.load(Ordering::Relaxed);
+        let tail = self.ring.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        // SAFETY: the acquire load of `tail` makes the producer's write to
+        // this slot visible, and the producer won't reuse it until `head`
+        // moves past it.
+        let value = unsafe { (*self.ring.slot(head)).assume_init_read() };
+        self.ring.head.store(head.wrapping_add(1), Ordering::Release);
+        Some(value)
+    }
+
+    /// Returns the shared ring, e.g. to query its length.
+    #[inline(always)]
+    pub fn ring(&self) -> &SpscRing<T> {
+        &self.ring
+    }
+}
+
+impl<T> fmt::Debug for SpscConsumer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SpscConsumer").field(&*self.ring).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use std::thread;
+
+    #[test]
+    fn test_fifo_and_wraparound() {
+        let (mut tx, mut rx) = SpscRing::new(3).split();
+        for round in 0..5 {
+            for i in 0..3 {
+                tx.push(round * 10 + i).unwrap();
+            }
+            assert_eq!(tx.push(99), Err(99));
+            assert_eq!(rx.ring().len(), 3);
+            for i in 0..3 {
+                assert_eq!(rx.pop(), Some(round * 10 + i));
+            }
+            assert_eq!(rx.pop(), None);
+        }
+    }
+
+    #[test]
+    fn test_producer_consumer_threads() {
+        const ITEMS: u64 = 100_000;
+
+        let (mut tx, mut rx) = SpscRing::new(64).split();
+        let producer = thread::spawn(move || {
+            for i in 0..ITEMS {
+                let mut value = i;
+                while let Err(back) = tx.push(value) {
+                    value = back;
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut expected = 0;
+        while expected < ITEMS {
+            match rx.pop() {
+                Some(value) => {
+                    assert_eq!(value, expected);
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        producer.join().unwrap();
+        assert_eq!(rx.pop(), None);
+    }
+
+    #[test]
+    fn test_drop_releases_queued_items() {
+        let marker = Rc::new(());
+        {
+            let ring = SpscRing::new(4);
+            let (mut tx, mut rx) = ring.split();
+            for _ in 0..3 {
+                tx.push(Rc::clone(&marker)).unwrap();
+            }
+            drop(rx.pop());
+            assert_eq!(Rc::strong_count(&marker), 3);
+        }
+        assert_eq!(Rc::strong_count(&marker), 1);
+    }
+}
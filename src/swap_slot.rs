@@ -0,0 +1,140 @@
+//! A replaceable shared value: `load` an `Arc`, `store` a new one.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::MutexExt;
+
+/// A slot holding an `Arc<T>` that can be swapped out while readers keep
+/// using the old one.
+///
+/// [`load`](SwapSlot::load) clones the current `Arc` under a short lock and
+/// releases it immediately, so readers work with a complete, immutable value
+/// for as long as they like, and a [`store`](SwapSlot::store) never waits
+/// for them. The old value is freed once the last reader drops its `Arc`.
+/// This is the hot-reload counterpart of a `OnceLock` global, for a value
+/// that is read often and replaced rarely.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::WrapExt;
+///     use std::sync::Arc;
+///
+///     let config = String::from("v1").swap_slot();
+///     let in_flight = config.load();
+///     config.store(Arc::new(String::from("v2")));
+///
+///     assert_eq!(*in_flight, "v1");
+///     assert_eq!(*config.load(), "v2");
+/// }
+/// ```
+pub struct SwapSlot<T: ?Sized> {
+    current: Mutex<Arc<T>>,
+}
+
+impl<T> SwapSlot<T> {
+    /// Creates a slot holding `value`.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        SwapSlot::from(Arc::new(value))
+    }
+}
+
+impl<T: ?Sized> SwapSlot<T> {
+    /// Returns the current value.
+    #[inline]
+    pub fn load(&self) -> Arc<T> {
+        Arc::clone(&self.current.lock_ignore_poison())
+    }
+
+    /// Replaces the current value. Earlier `load`s keep the old value.
+    #[inline]
+    pub fn store(&self, value: Arc<T>) {
+        drop(self.swap(value));
+    }
+
+    /// Replaces the current value, returning the previous one.
+    #[inline]
+    pub fn swap(&self, value: Arc<T>) -> Arc<T> {
+        // The old `Arc` is returned, so it is never freed under the lock.
+        core::mem::replace(&mut *self.current.lock_ignore_poison(), value)
+    }
+
+    /// Consumes the slot, returning the current value.
+    #[inline]
+    pub fn into_inner(self) -> Arc<T> {
+        self.current
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl<T: ?Sized> From<Arc<T>> for SwapSlot<T> {
+    #[inline]
+    fn from(value: Arc<T>) -> Self {
+        SwapSlot {
+            current: Mutex::new(value),
+        }
+    }
+}
+
+impl<T: Default> Default for SwapSlot<T> {
+    #[inline]
+    fn default() -> Self {
+        SwapSlot::new(T::default())
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for SwapSlot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SwapSlot").field(&&*self.load()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+
+    #[test]
+    fn test_readers_see_whole_values_during_stores() {
+        // Every generation is `[n; 64]`; a torn value would mix two of them.
+        let slot = SwapSlot::new(vec![0u32; 64]);
+        let done = AtomicBool::new(false);
+
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    let mut last = 0;
+                    while !done.load(Ordering::Acquire) {
+                        let value = slot.load();
+                        assert!(value.iter().all(|&n| n == value[0]), "torn value");
+                        assert!(value[0] >= last, "went back in time");
+                        last = value[0];
+                    }
+                });
+            }
+            s.spawn(|| {
+                for n in 1..=500 {
+                    slot.store(Arc::new(vec![n; 64]));
+                }
+                done.store(true, Ordering::Release);
+            });
+        });
+
+        assert_eq!(slot.load()[0], 500);
+    }
+
+    #[test]
+    fn test_swap_returns_previous() {
+        let slot: SwapSlot<str> = SwapSlot::from(Arc::from("old"));
+        let old = slot.swap(Arc::from("new"));
+        assert_eq!(&*old, "old");
+        assert_eq!(format!("{:?}", slot), "SwapSlot(\"new\")");
+        assert_eq!(&*slot.into_inner(), "new");
+    }
+}
@@ -0,0 +1,123 @@
+//! A single-threaded string interner.
+
+use std::cell::{Ref, RefCell};
+use std::collections::HashMap;
+
+/// Interns strings into small integer symbols.
+///
+/// Interning the same string twice returns the same symbol, and symbols are
+/// handed out densely starting at `0`. All methods take `&self`, so a table
+/// can be shared by reference throughout a single-threaded pass.
+///
+/// # Panics
+///
+/// [`resolve`](SymbolTable::resolve) returns a `Ref` into the table. Calling
+/// [`intern`](SymbolTable::intern) while such a `Ref` is alive panics, so copy
+/// the string out first if the table is still being filled.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::SymbolTable;
+///
+///     let table = SymbolTable::new();
+///     let a = table.intern("alpha");
+///     assert_eq!(table.intern("alpha"), a);
+///     assert_eq!(&*table.resolve(a).unwrap(), "alpha");
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct SymbolTable {
+    inner: RefCell<(Vec<String>, HashMap<String, u32>)>,
+}
+
+impl SymbolTable {
+    /// Creates an empty symbol table.
+    #[inline]
+    pub fn new() -> Self {
+        SymbolTable::default()
+    }
+
+    /// Returns the symbol for `name`, interning it if it is new.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u32::MAX` distinct strings are interned, or if a
+    /// `Ref` returned by [`resolve`](SymbolTable::resolve) is still alive.
+    pub fn intern(&self, name: &str) -> u32 {
+        let mut inner = self.inner.borrow_mut();
+        let (names, ids) = &mut *inner;
+        if let Some(&id) = ids.get(name) {
+            return id;
+        }
+        let id = u32::try_from(names.len()).expect("symbol table overflow");
+        names.push(name.to_owned());
+        ids.insert(name.to_owned(), id);
+        id
+    }
+
+    /// Returns the symbol for `name` if it has already been interned.
+    pub fn get(&self, name: &str) -> Option<u32> {
+        self.inner.borrow().1.get(name).copied()
+    }
+
+    /// Returns the string for `symbol`, or `None` if it was never handed out.
+    pub fn resolve(&self, symbol: u32) -> Option<Ref<'_, str>> {
+        Ref::filter_map(self.inner.borrow(), |(names, _)| {
+            names.get(symbol as usize).map(String::as_str)
+        })
+        .ok()
+    }
+
+    /// Returns the number of interned strings.
+    pub fn len(&self) -> usize {
+        self.inner.borrow().0.len()
+    }
+
+    /// Returns `true` if nothing has been interned yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_duplicates() {
+        let table = SymbolTable::new();
+        let foo = table.intern("foo");
+        let bar = table.intern("bar");
+
+        assert_eq!(foo, 0);
+        assert_eq!(bar, 1);
+        assert_eq!(table.intern("foo"), foo);
+        assert_eq!(table.intern("bar"), bar);
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get("foo"), Some(foo));
+        assert_eq!(table.get("baz"), None);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let table = SymbolTable::new();
+        let ids: Vec<u32> = ["x", "y", "x", "z"].iter().map(|s| table.intern(s)).collect();
+        assert_eq!(ids, [0, 1, 0, 2]);
+
+        assert_eq!(&*table.resolve(0).unwrap(), "x");
+        assert_eq!(&*table.resolve(2).unwrap(), "z");
+        assert!(table.resolve(3).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_intern_while_resolved_panics() {
+        let table = SymbolTable::new();
+        let id = table.intern("held");
+        let _held = table.resolve(id);
+        table.intern("other");
+    }
+}
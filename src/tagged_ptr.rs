@@ -0,0 +1,195 @@
+//! An ABA-resistant atomic pointer packing a version tag next to the address.
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+const PTR_BITS: u32 = 48;
+const PTR_MASK: u64 = (1 << PTR_BITS) - 1;
+
+/// An atomic `(pointer, tag)` pair stored in a single `AtomicU64`.
+///
+/// The low 48 bits hold the address and the high 16 bits a version tag.
+/// Every successful [`compare_exchange`](TaggedPtr::compare_exchange) bumps
+/// the tag, so a thread holding a stale `(ptr, tag)` snapshot fails its CAS
+/// even if the same address has since been reinstalled (the ABA problem).
+///
+/// Addresses must fit in 48 bits, which holds for user-space pointers on
+/// x86-64 and AArch64 as well as all 32-bit targets. It does not hold for
+/// 5-level-paging (LA57) mappings on x86-64 or for top-byte-tagged (TBI/MTE)
+/// pointers on AArch64; every method that takes a pointer panics on such an
+/// address rather than silently truncating it. Tags wrap after 65536
+/// updates.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::TaggedPtr;
+///
+///     let mut a = 1u32;
+///     let mut b = 2u32;
+///     let (pa, pb) = (&mut a as *mut u32, &mut b as *mut u32);
+///
+///     let head = TaggedPtr::new(pa);
+///     let stale = head.load();
+///     head.compare_exchange(stale, pb).unwrap();
+///     head.compare_exchange(head.load(), pa).unwrap();
+///
+///     // Same pointer, older tag: rejected.
+///     assert!(head.compare_exchange(stale, pb).is_err());
+/// }
+/// ```
+pub struct TaggedPtr<T> {
+    bits: AtomicU64,
+    _marker: PhantomData<*mut T>,
+}
+
+// SAFETY: like `AtomicPtr<T>`, only the address is shared; dereferencing it is
+// the caller's responsibility.
+unsafe impl<T> Send for TaggedPtr<T> {}
+unsafe impl<T> Sync for TaggedPtr<T> {}
+
+#[inline(always)]
+#[track_caller]
+fn pack<T>(ptr: *mut T, tag: u16) -> u64 {
+    let addr = ptr as usize as u64;
+    assert!(addr & !PTR_MASK == 0, "pointer does not fit in {} bits", PTR_BITS);
+    (addr & PTR_MASK) | ((tag as u64) << PTR_BITS)
+}
+
+#[inline(always)]
+fn unpack<T>(bits: u64) -> (*mut T, u16) {
+    ((bits & PTR_MASK) as usize as *mut T, (bits >> PTR_BITS) as u16)
+}
+
+impl<T> TaggedPtr<T> {
+    /// Creates a tagged pointer with tag `0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the address of `ptr` does not fit in 48 bits.
+    #[inline(always)]
+    #[track_caller]
+    pub fn new(ptr: *mut T) -> Self {
+        TaggedPtr {
+            bits: AtomicU64::new(pack(ptr, 0)),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Loads the current pointer and tag (`Acquire`).
+    #[inline(always)]
+    pub fn load(&self) -> (*mut T, u16) {
+        unpack(self.bits.load(Ordering::Acquire))
+    }
+
+    /// Stores `ptr`, bumping the tag (`AcqRel`), and returns the previous pair.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the address of `ptr` does not fit in 48 bits.
+    #[inline]
+    #[track_caller]
+    pub fn store(&self, ptr: *mut T) -> (*mut T, u16) {
+        let mut current = self.bits.load(Ordering::Relaxed);
+        loop {
+            let (_, tag) = unpack::<T>(current);
+            let next = pack(ptr, tag.wrapping_add(1));
+            match self.bits.compare_exchange_weak(current, next, Ordering::AcqRel, Ordering::Relaxed) {
+                Ok(previous) => return unpack(previous),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Installs `new` if both the pointer and tag still equal `current`.
+    ///
+    /// On success the tag is incremented and the previous pair is returned;
+    /// on failure the actual pair is returned. Uses `AcqRel` on success and
+    /// `Acquire` on failure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the address of `current.0` or `new` does not fit in 48
+    /// bits.
+    #[inline]
+    #[track_caller]
+    pub fn compare_exchange(&self, current: (*mut T, u16), new: *mut T) -> Result<(*mut T, u16), (*mut T, u16)> {
+        let expected = pack(current.0, current.1);
+        let next = pack(new, current.1.wrapping_add(1));
+        self.bits
+            .compare_exchange(expected, next, Ordering::AcqRel, Ordering::Acquire)
+            .map(unpack)
+            .map_err(unpack)
+    }
+}
+
+impl<T> fmt::Debug for TaggedPtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (ptr, tag) = self.load();
+        f.debug_struct("TaggedPtr").field("ptr", &ptr).field("tag", &tag).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_roundtrip() {
+        let mut value = 5u64;
+        let ptr = &mut value as *mut u64;
+        let tagged = TaggedPtr::new(ptr);
+        assert_eq!(tagged.load(), (ptr, 0));
+
+        let null = TaggedPtr::<u64>::new(core::ptr::null_mut());
+        assert_eq!(null.load(), (core::ptr::null_mut(), 0));
+    }
+
+    #[test]
+    fn test_stale_tag_rejected() {
+        let mut a = 1u32;
+        let mut b = 2u32;
+        let (pa, pb) = (&mut a as *mut u32, &mut b as *mut u32);
+
+        let head = TaggedPtr::new(pa);
+        let stale = head.load();
+
+        // A -> B -> A: the address is back, but the tag has moved on.
+        assert_eq!(head.compare_exchange(stale, pb), Ok((pa, 0)));
+        assert_eq!(head.compare_exchange((pb, 1), pa), Ok((pb, 1)));
+        assert_eq!(head.load(), (pa, 2));
+
+        assert_eq!(head.compare_exchange(stale, pb), Err((pa, 2)));
+        assert_eq!(head.load(), (pa, 2));
+    }
+
+    #[test]
+    fn test_store_bumps_tag() {
+        let mut a = 0u8;
+        let pa = &mut a as *mut u8;
+        let tagged = TaggedPtr::new(core::ptr::null_mut());
+        assert_eq!(tagged.store(pa), (core::ptr::null_mut(), 0));
+        assert_eq!(tagged.load(), (pa, 1));
+    }
+
+    #[test]
+    fn test_tag_wraps() {
+        let tagged = TaggedPtr::<u8>::new(core::ptr::null_mut());
+        for expected in 0..=u16::MAX {
+            assert_eq!(tagged.load().1, expected);
+            tagged.store(core::ptr::null_mut());
+        }
+        assert_eq!(tagged.load().1, 0);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    #[should_panic(expected = "pointer does not fit in 48 bits")]
+    fn test_wide_address_panics() {
+        let tagged = TaggedPtr::<u8>::new(core::ptr::null_mut());
+        tagged.store((1usize << 48) as *mut u8);
+    }
+}
@@ -0,0 +1,139 @@
+//! A single-threaded lazily evaluated value.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::mem;
+
+enum ThunkState<T, F> {
+    Unevaluated(F),
+    Evaluating,
+    Evaluated(T),
+}
+
+/// A value computed by a closure on first [`force`](Thunk::force) and cached
+/// afterwards.
+///
+/// The closure runs at most once. `force` hands out clones of the cached
+/// value, so thunks can be forced from several places through `&self`.
+///
+/// # Panics
+///
+/// Forcing a thunk from inside its own closure, or after its closure
+/// panicked, panics.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::Thunk;
+///
+///     let total = Thunk::new(|| (1..=10).sum::<u32>());
+///     assert!(!total.is_evaluated());
+///     assert_eq!(total.force(), 55);
+///     assert!(total.is_evaluated());
+/// }
+/// ```
+pub struct Thunk<T: Clone, F = Box<dyn FnOnce() -> T>> {
+    state: RefCell<ThunkState<T, F>>,
+}
+
+impl<T: Clone, F: FnOnce() -> T> Thunk<T, F> {
+    /// Creates an unevaluated thunk.
+    #[inline(always)]
+    pub fn new(f: F) -> Self {
+        Thunk {
+            state: RefCell::new(ThunkState::Unevaluated(f)),
+        }
+    }
+
+    /// Evaluates the thunk if needed and returns a clone of its value.
+    pub fn force(&self) -> T {
+        let f = {
+            let mut state = self.state.borrow_mut();
+            match mem::replace(&mut *state, ThunkState::Evaluating) {
+                ThunkState::Evaluated(value) => {
+                    let result = value.clone();
+                    *state = ThunkState::Evaluated(value);
+                    return result;
+                }
+                ThunkState::Unevaluated(f) => f,
+                ThunkState::Evaluating => panic!("Thunk forced recursively or after a panic"),
+            }
+        };
+
+        // The borrow is released while the closure runs so that it may force
+        // other thunks; re-forcing this one hits the `Evaluating` state above.
+        let value = f();
+        *self.state.borrow_mut() = ThunkState::Evaluated(value.clone());
+        value
+    }
+}
+
+impl<T: Clone, F> Thunk<T, F> {
+    /// Creates a thunk that is already evaluated.
+    #[inline(always)]
+    pub fn evaluated(value: T) -> Self {
+        Thunk {
+            state: RefCell::new(ThunkState::Evaluated(value)),
+        }
+    }
+
+    /// Returns `true` once the closure has produced a value.
+    #[inline]
+    pub fn is_evaluated(&self) -> bool {
+        matches!(*self.state.borrow(), ThunkState::Evaluated(_))
+    }
+}
+
+impl<T: Clone + fmt::Debug, F> fmt::Debug for Thunk<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &*self.state.borrow() {
+            ThunkState::Evaluated(value) => f.debug_tuple("Thunk").field(value).finish(),
+            _ => f.write_str("Thunk(<unevaluated>)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_closure_runs_once() {
+        let calls = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&calls);
+        let thunk = Thunk::new(move || {
+            counter.set(counter.get() + 1);
+            String::from("computed")
+        });
+
+        assert_eq!(calls.get(), 0);
+        assert_eq!(thunk.force(), "computed");
+        assert_eq!(thunk.force(), "computed");
+        assert_eq!(thunk.force(), "computed");
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_boxed_and_evaluated() {
+        let boxed: Thunk<u32> = Thunk::new(Box::new(|| 3));
+        assert_eq!(boxed.force(), 3);
+
+        let ready: Thunk<u32> = Thunk::evaluated(4);
+        assert!(ready.is_evaluated());
+        assert_eq!(ready.force(), 4);
+        assert_eq!(format!("{:?}", ready), "Thunk(4)");
+    }
+
+    #[test]
+    fn test_nested_thunks() {
+        let base = Rc::new(Thunk::new(|| 20u32));
+        let inner = Rc::clone(&base);
+        let derived = Thunk::new(move || inner.force() + 1);
+        assert_eq!(derived.force(), 21);
+        assert!(base.is_evaluated());
+    }
+}
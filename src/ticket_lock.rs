@@ -0,0 +1,240 @@
+//! A FIFO-fair spinlock built on two `AtomicUsize` counters.
+//!
+//! Only `core` is used, so the lock is usable in `no_std` environments.
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::hint::spin_loop;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A mutual-exclusion lock that grants access in the order it was requested.
+///
+/// Each call to [`lock`](TicketLock::lock) draws the next ticket and spins
+/// until that ticket is being served, so waiters acquire the lock strictly
+/// first-come, first-served and none can starve. Like [`SpinLock`], it
+/// busy-waits and has no poisoning.
+///
+/// [`SpinLock`]: crate::SpinLock
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::TicketLock;
+///
+///     let lock = TicketLock::new(Vec::new());
+///     lock.lock().push(1);
+///     lock.lock().push(2);
+///     assert_eq!(*lock.lock(), [1, 2]);
+/// }
+/// ```
+pub struct TicketLock<T: ?Sized> {
+    next_ticket: AtomicUsize,
+    now_serving: AtomicUsize,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: access to `value` is serialized by the ticket counters, so sharing
+// the lock only requires the protected value to be sendable between threads.
+unsafe impl<T: ?Sized + Send> Send for TicketLock<T> {}
+unsafe impl<T: ?Sized + Send> Sync for TicketLock<T> {}
+
+impl<T> TicketLock<T> {
+    /// Creates a new unlocked ticket lock holding `value`.
+    #[inline(always)]
+    pub const fn new(value: T) -> Self {
+        TicketLock {
+            next_ticket: AtomicUsize::new(0),
+            now_serving: AtomicUsize::new(0),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Consumes the lock, returning the protected value.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: ?Sized> TicketLock<T> {
+    /// Draws a ticket and spins until it is served.
+    #[inline]
+    pub fn lock(&self) -> TicketGuard<'_, T> {
+        let ticket = self.next_ticket.fetch_add(1, Ordering::Relaxed);
+        while self.now_serving.load(Ordering::Acquire) != ticket {
+            spin_loop();
+        }
+        TicketGuard { lock: self }
+    }
+
+    /// Acquires the lock only if nobody holds it or is waiting for it.
+    #[inline]
+    pub fn try_lock(&self) -> Option<TicketGuard<'_, T>> {
+        // This `Acquire` load pairs with the previous holder's `Release` in
+        // `TicketGuard::drop`, and is what synchronizes with its writes. The
+        // CAS on `next_ticket` only claims the ticket: that counter is
+        // written with `Relaxed` alone and carries no happens-before edge.
+        let serving = self.now_serving.load(Ordering::Acquire);
+        self.next_ticket
+            .compare_exchange(
+                serving,
+                serving.wrapping_add(1),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .ok()
+            .map(|_| TicketGuard { lock: self })
+    }
+
+    /// Returns `true` if the lock is currently held.
+    #[inline(always)]
+    pub fn is_locked(&self) -> bool {
+        self.queue_len() != 0
+    }
+
+    /// Returns the number of tickets drawn but not yet released, counting
+    /// the current holder and every waiter.
+    #[inline]
+    pub fn queue_len(&self) -> usize {
+        let next = self.next_ticket.load(Ordering::Relaxed);
+        next.wrapping_sub(self.now_serving.load(Ordering::Relaxed))
+    }
+
+    /// Returns a mutable reference to the protected value.
+    ///
+    /// No locking is needed since the borrow is exclusive.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+}
+
+impl<T: Default> Default for TicketLock<T> {
+    fn default() -> Self {
+        TicketLock::new(T::default())
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for TicketLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("TicketLock");
+        match self.try_lock() {
+            Some(guard) => d.field("value", &&*guard),
+            None => d.field("value", &format_args!("<locked>")),
+        };
+        d.finish()
+    }
+}
+
+/// RAII guard returned by [`TicketLock::lock`]; serves the next ticket on drop.
+pub struct TicketGuard<'a, T: ?Sized> {
+    lock: &'a TicketLock<T>,
+}
+
+// SAFETY: the guard only hands out `&T` when shared, so `T: Sync` suffices.
+unsafe impl<T: ?Sized + Sync> Sync for TicketGuard<'_, T> {}
+
+impl<T: ?Sized> Deref for TicketGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // SAFETY: holding the guard means our ticket is being served.
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for TicketGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: holding the guard means our ticket is being served, and
+        // only one ticket is served at a time.
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for TicketGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.lock.now_serving.fetch_add(1, Ordering::Release);
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for TicketGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_lock_and_try_lock() {
+        let lock = TicketLock::new(1u32);
+        {
+            let mut guard = lock.lock();
+            *guard += 1;
+            assert!(lock.is_locked());
+            assert!(lock.try_lock().is_none());
+        }
+        assert!(!lock.is_locked());
+        assert_eq!(*lock.try_lock().unwrap(), 2);
+        assert_eq!(lock.into_inner(), 2);
+    }
+
+    #[test]
+    fn test_acquisition_follows_ticket_order() {
+        const WAITERS: usize = 6;
+
+        let lock = Arc::new(TicketLock::new(Vec::new()));
+        let holder = lock.lock();
+
+        let handles: Vec<_> = (0..WAITERS)
+            .map(|i| {
+                let waiter = Arc::clone(&lock);
+                let handle = thread::spawn(move || waiter.lock().push(i));
+                // Wait until this thread has drawn its ticket before starting
+                // the next one, so ticket order is known.
+                while lock.queue_len() != i + 2 {
+                    thread::yield_now();
+                }
+                handle
+            })
+            .collect();
+
+        drop(holder);
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*lock.lock(), (0..WAITERS).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_contended_counter() {
+        const ITERS: u32 = 10_000;
+
+        let lock = Arc::new(TicketLock::new(0u32));
+        let handles: Vec<_> = (0..3)
+            .map(|_| {
+                let lock = Arc::clone(&lock);
+                thread::spawn(move || {
+                    for _ in 0..ITERS {
+                        *lock.lock() += 1;
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*lock.lock(), 3 * ITERS);
+    }
+}
@@ -0,0 +1,253 @@
+//! A mutex that detects inconsistent lock ordering in debug builds.
+//!
+//! With the `deadlock_debug` feature in a build with debug assertions,
+//! [`TrackedMutex`] records which tracked mutexes each thread already holds
+//! whenever it acquires another one. The "held while acquiring" pairs form a
+//! global lock-order graph; an acquisition that would close a cycle in that
+//! graph (the classic "A then B here, B then A there") panics before it
+//! blocks, naming both orders. Otherwise `TrackedMutex<T>` is a plain
+//! `Mutex<T>` with no overhead.
+
+#[cfg(not(all(feature = "deadlock_debug", debug_assertions)))]
+/// A `Mutex<T>`; lock-order tracking is compiled out in this build.
+///
+/// Enable the `deadlock_debug` feature in a debug build to get cycle
+/// detection.
+pub type TrackedMutex<T> = std::sync::Mutex<T>;
+
+#[cfg(all(feature = "deadlock_debug", debug_assertions))]
+pub use tracking::{TrackedGuard, TrackedMutex};
+
+#[cfg(all(feature = "deadlock_debug", debug_assertions))]
+mod tracking {
+    use std::cell::RefCell;
+    use std::collections::{HashMap, HashSet};
+    use std::fmt;
+    use std::ops::{Deref, DerefMut};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{LockResult, Mutex, MutexGuard, PoisonError, TryLockError, TryLockResult};
+
+    use crate::MutexExt;
+
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    /// Edges `a -> b` meaning "`b` was acquired while `a` was held".
+    static LOCK_ORDER: Mutex<Option<HashMap<usize, HashSet<usize>>>> = Mutex::new(None);
+
+    thread_local! {
+        static HELD: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn reaches(graph: &HashMap<usize, HashSet<usize>>, from: usize, to: usize) -> bool {
+        let mut stack = vec![from];
+        let mut seen = HashSet::new();
+        while let Some(node) = stack.pop() {
+            if node == to {
+                return true;
+            }
+            if seen.insert(node) {
+                if let Some(next) = graph.get(&node) {
+                    stack.extend(next.iter().copied());
+                }
+            }
+        }
+        false
+    }
+
+    /// Records that the current thread is about to acquire `id`, panicking if
+    /// that contradicts an order seen before.
+    fn check_and_record(id: usize) {
+        let held = HELD.with(|held| held.borrow().clone());
+        if held.is_empty() {
+            return;
+        }
+
+        let mut order = LOCK_ORDER.lock_ignore_poison();
+        let graph = order.get_or_insert_with(HashMap::new);
+        for &before in &held {
+            if before != id && reaches(graph, id, before) {
+                drop(order);
+                panic!(
+                    "lock order inversion: acquiring tracked mutex #{} while holding #{}, \
+                     but #{} has previously been acquired while holding #{}",
+                    id, before, before, id
+                );
+            }
+        }
+        for &before in &held {
+            if before != id {
+                graph.entry(before).or_default().insert(id);
+            }
+        }
+    }
+
+    /// A `Mutex<T>` that records acquisition order and panics on a potential
+    /// deadlock.
+    pub struct TrackedMutex<T: ?Sized> {
+        id: usize,
+        inner: Mutex<T>,
+    }
+
+    impl<T> TrackedMutex<T> {
+        /// Creates a new tracked mutex.
+        pub fn new(value: T) -> Self {
+            TrackedMutex {
+                id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+                inner: Mutex::new(value),
+            }
+        }
+
+        /// Consumes the mutex, returning the protected value.
+        #[inline]
+        pub fn into_inner(self) -> LockResult<T> {
+            self.inner.into_inner()
+        }
+    }
+
+    impl<T: ?Sized> TrackedMutex<T> {
+        /// Acquires the lock like `Mutex::lock`.
+        ///
+        /// # Panics
+        ///
+        /// Panics if, while holding tracked mutex A, this acquires B after some
+        /// thread has previously acquired A while holding B.
+        pub fn lock(&self) -> LockResult<TrackedGuard<'_, T>> {
+            check_and_record(self.id);
+            match self.inner.lock() {
+                Ok(guard) => Ok(self.track(guard)),
+                Err(poisoned) => Err(PoisonError::new(self.track(poisoned.into_inner()))),
+            }
+        }
+
+        /// Attempts to acquire the lock like `Mutex::try_lock`.
+        ///
+        /// A `try_lock` can't block, so it never deadlocks and is not checked,
+        /// but a successful one is recorded for later acquisitions.
+        pub fn try_lock(&self) -> TryLockResult<TrackedGuard<'_, T>> {
+            match self.inner.try_lock() {
+                Ok(guard) => Ok(self.track(guard)),
+                Err(TryLockError::Poisoned(poisoned)) => Err(TryLockError::Poisoned(
+                    PoisonError::new(self.track(poisoned.into_inner())),
+                )),
+                Err(TryLockError::WouldBlock) => Err(TryLockError::WouldBlock),
+            }
+        }
+
+        /// Returns a mutable reference to the protected value.
+        #[inline]
+        pub fn get_mut(&mut self) -> LockResult<&mut T> {
+            self.inner.get_mut()
+        }
+
+        fn track<'a>(&'a self, guard: MutexGuard<'a, T>) -> TrackedGuard<'a, T> {
+            HELD.with(|held| held.borrow_mut().push(self.id));
+            TrackedGuard { id: self.id, guard }
+        }
+    }
+
+    impl<T: Default> Default for TrackedMutex<T> {
+        fn default() -> Self {
+            TrackedMutex::new(T::default())
+        }
+    }
+
+    impl<T: ?Sized + fmt::Debug> fmt::Debug for TrackedMutex<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("TrackedMutex")
+                .field("id", &self.id)
+                .field("inner", &&self.inner)
+                .finish()
+        }
+    }
+
+    /// Guard returned by [`TrackedMutex::lock`].
+    pub struct TrackedGuard<'a, T: ?Sized> {
+        id: usize,
+        guard: MutexGuard<'a, T>,
+    }
+
+    impl<T: ?Sized> Deref for TrackedGuard<'_, T> {
+        type Target = T;
+
+        #[inline(always)]
+        fn deref(&self) -> &T {
+            &self.guard
+        }
+    }
+
+    impl<T: ?Sized> DerefMut for TrackedGuard<'_, T> {
+        #[inline(always)]
+        fn deref_mut(&mut self) -> &mut T {
+            &mut self.guard
+        }
+    }
+
+    impl<T: ?Sized> Drop for TrackedGuard<'_, T> {
+        fn drop(&mut self) {
+            // `try_with` because guards may be dropped during thread teardown.
+            let _ = HELD.try_with(|held| {
+                let mut held = held.borrow_mut();
+                if let Some(pos) = held.iter().rposition(|&id| id == self.id) {
+                    held.remove(pos);
+                }
+            });
+        }
+    }
+
+    impl<T: ?Sized + fmt::Debug> fmt::Debug for TrackedGuard<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(&**self, f)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_consistent_order_is_allowed() {
+        let a = Arc::new(TrackedMutex::new(1u32));
+        let b = Arc::new(TrackedMutex::new(2u32));
+
+        for _ in 0..2 {
+            let (a, b) = (Arc::clone(&a), Arc::clone(&b));
+            thread::spawn(move || {
+                let ga = a.lock().unwrap();
+                let mut gb = b.lock().unwrap();
+                *gb += *ga;
+            })
+            .join()
+            .unwrap();
+        }
+        assert_eq!(*b.lock().unwrap(), 4);
+    }
+
+    #[cfg(all(feature = "deadlock_debug", debug_assertions))]
+    #[test]
+    fn test_opposite_order_panics() {
+        let a = Arc::new(TrackedMutex::new(()));
+        let b = Arc::new(TrackedMutex::new(()));
+
+        let (a1, b1) = (Arc::clone(&a), Arc::clone(&b));
+        thread::spawn(move || {
+            let _a = a1.lock().unwrap();
+            let _b = b1.lock().unwrap();
+        })
+        .join()
+        .unwrap();
+
+        let (a2, b2) = (Arc::clone(&a), Arc::clone(&b));
+        let err = thread::spawn(move || {
+            let _b = b2.lock().unwrap();
+            let _a = a2.lock().unwrap();
+        })
+        .join()
+        .unwrap_err();
+
+        let message = err.downcast_ref::<String>().unwrap();
+        assert!(message.starts_with("lock order inversion"), "{}", message);
+    }
+}
@@ -0,0 +1,344 @@
+//! A `RefCell` that remembers where it was last borrowed, in debug builds.
+//!
+//! With the `debug_refcell` feature in a build with debug assertions,
+//! [`TrackedRefCell`] records the `#[track_caller]` location of each borrow
+//! until the last outstanding guard is dropped. A conflicting borrow then
+//! panics with both sites, and [`TrackedRefCell::borrowed_at`] reports the
+//! site directly, which points at the `Ref`/`RefMut` that leaked. Otherwise
+//! the borrows go straight to the inner `RefCell`, the guards are plain
+//! `Ref`/`RefMut` and `borrowed_at` always returns `None`.
+
+use core::panic::Location;
+use std::cell::RefCell;
+use std::fmt;
+
+#[cfg(not(all(feature = "debug_refcell", debug_assertions)))]
+use std::cell::{BorrowError, BorrowMutError};
+
+#[cfg(not(all(feature = "debug_refcell", debug_assertions)))]
+/// Shared guard returned by [`TrackedRefCell::borrow`]; a plain `Ref` in
+/// this build.
+pub type TrackedRef<'a, T> = std::cell::Ref<'a, T>;
+
+#[cfg(not(all(feature = "debug_refcell", debug_assertions)))]
+/// Exclusive guard returned by [`TrackedRefCell::borrow_mut`]; a plain
+/// `RefMut` in this build.
+pub type TrackedRefMut<'a, T> = std::cell::RefMut<'a, T>;
+
+#[cfg(all(feature = "debug_refcell", debug_assertions))]
+pub use tracking::{TrackedRef, TrackedRefMut};
+
+/// A `RefCell<T>` that can report where it is currently borrowed.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "std")]
+/// {
+///     use autowrap::WrapExt;
+///
+///     let cache = Vec::<u32>::new().tracked_refcell();
+///     cache.borrow_mut().push(1);
+///     assert!(cache.borrowed_at().is_none());
+///
+///     let leaked = cache.borrow();
+///     // `Some(location of the line above)` with `debug_refcell` in a debug
+///     // build, `None` otherwise.
+///     let _site = cache.borrowed_at();
+///     drop(leaked);
+/// }
+/// ```
+pub struct TrackedRefCell<T: ?Sized> {
+    #[cfg(all(feature = "debug_refcell", debug_assertions))]
+    site: core::cell::Cell<Option<&'static Location<'static>>>,
+    inner: RefCell<T>,
+}
+
+impl<T> TrackedRefCell<T> {
+    /// Creates a new tracked cell.
+    #[inline(always)]
+    pub fn new(value: T) -> Self {
+        TrackedRefCell {
+            #[cfg(all(feature = "debug_refcell", debug_assertions))]
+            site: core::cell::Cell::new(None),
+            inner: RefCell::new(value),
+        }
+    }
+
+    /// Consumes the cell, returning the value.
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.inner.into_inner()
+    }
+}
+
+impl<T: ?Sized> TrackedRefCell<T> {
+    /// Returns a mutable reference to the value; no borrow is recorded since
+    /// the access is statically exclusive.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.inner.get_mut()
+    }
+}
+
+#[cfg(not(all(feature = "debug_refcell", debug_assertions)))]
+impl<T: ?Sized> TrackedRefCell<T> {
+    /// Immutably borrows the value, like `RefCell::borrow`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is mutably borrowed.
+    #[inline(always)]
+    #[track_caller]
+    pub fn borrow(&self) -> TrackedRef<'_, T> {
+        self.inner.borrow()
+    }
+
+    /// Mutably borrows the value, like `RefCell::borrow_mut`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is borrowed at all.
+    #[inline(always)]
+    #[track_caller]
+    pub fn borrow_mut(&self) -> TrackedRefMut<'_, T> {
+        self.inner.borrow_mut()
+    }
+
+    /// Immutably borrows the value, failing if it is mutably borrowed.
+    #[inline(always)]
+    #[track_caller]
+    pub fn try_borrow(&self) -> Result<TrackedRef<'_, T>, BorrowError> {
+        self.inner.try_borrow()
+    }
+
+    /// Mutably borrows the value, failing if it is borrowed at all.
+    #[inline(always)]
+    #[track_caller]
+    pub fn try_borrow_mut(&self) -> Result<TrackedRefMut<'_, T>, BorrowMutError> {
+        self.inner.try_borrow_mut()
+    }
+
+    /// Returns where the value is currently borrowed; always `None` without
+    /// the `debug_refcell` feature in a debug build.
+    #[inline(always)]
+    pub fn borrowed_at(&self) -> Option<&'static Location<'static>> {
+        None
+    }
+}
+
+#[cfg(all(feature = "debug_refcell", debug_assertions))]
+mod tracking {
+    use super::TrackedRefCell;
+    use core::mem::ManuallyDrop;
+    use core::panic::Location;
+    use std::cell::{BorrowError, BorrowMutError, Ref, RefMut};
+    use std::fmt;
+    use std::ops::{Deref, DerefMut};
+
+    impl<T: ?Sized> TrackedRefCell<T> {
+        /// Immutably borrows the value and records the caller's location.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the value is mutably borrowed, naming where.
+        #[track_caller]
+        pub fn borrow(&self) -> TrackedRef<'_, T> {
+            match self.try_borrow() {
+                Ok(guard) => guard,
+                Err(err) => panic!("{} (mutable borrow taken at {})", err, self.site_name()),
+            }
+        }
+
+        /// Mutably borrows the value and records the caller's location.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the value is borrowed at all, naming where.
+        #[track_caller]
+        pub fn borrow_mut(&self) -> TrackedRefMut<'_, T> {
+            match self.try_borrow_mut() {
+                Ok(guard) => guard,
+                Err(err) => panic!("{} (borrow taken at {})", err, self.site_name()),
+            }
+        }
+
+        /// Immutably borrows the value, failing if it is mutably borrowed.
+        #[track_caller]
+        pub fn try_borrow(&self) -> Result<TrackedRef<'_, T>, BorrowError> {
+            let guard = self.inner.try_borrow()?;
+            self.site.set(Some(Location::caller()));
+            Ok(TrackedRef {
+                cell: self,
+                guard: ManuallyDrop::new(guard),
+            })
+        }
+
+        /// Mutably borrows the value, failing if it is borrowed at all.
+        #[track_caller]
+        pub fn try_borrow_mut(&self) -> Result<TrackedRefMut<'_, T>, BorrowMutError> {
+            let guard = self.inner.try_borrow_mut()?;
+            self.site.set(Some(Location::caller()));
+            Ok(TrackedRefMut {
+                cell: self,
+                guard: ManuallyDrop::new(guard),
+            })
+        }
+
+        /// Returns where the value is currently borrowed: the site of the
+        /// most recent borrow, while any borrow is outstanding.
+        #[inline]
+        pub fn borrowed_at(&self) -> Option<&'static Location<'static>> {
+            self.site.get()
+        }
+
+        fn site_name(&self) -> String {
+            match self.site.get() {
+                Some(site) => site.to_string(),
+                None => String::from("<unknown>"),
+            }
+        }
+
+        fn release(&self) {
+            if self.inner.try_borrow_mut().is_ok() {
+                self.site.set(None);
+            }
+        }
+    }
+
+    /// Shared guard returned by [`TrackedRefCell::borrow`].
+    pub struct TrackedRef<'a, T: ?Sized> {
+        cell: &'a TrackedRefCell<T>,
+        guard: ManuallyDrop<Ref<'a, T>>,
+    }
+
+    impl<T: ?Sized> Deref for TrackedRef<'_, T> {
+        type Target = T;
+
+        #[inline(always)]
+        fn deref(&self) -> &T {
+            &self.guard
+        }
+    }
+
+    impl<T: ?Sized> Drop for TrackedRef<'_, T> {
+        fn drop(&mut self) {
+            // SAFETY: the guard is dropped exactly once, here.
+            unsafe { ManuallyDrop::drop(&mut self.guard) };
+            self.cell.release();
+        }
+    }
+
+    impl<T: ?Sized + fmt::Debug> fmt::Debug for TrackedRef<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(&**self, f)
+        }
+    }
+
+    /// Exclusive guard returned by [`TrackedRefCell::borrow_mut`].
+    pub struct TrackedRefMut<'a, T: ?Sized> {
+        cell: &'a TrackedRefCell<T>,
+        guard: ManuallyDrop<RefMut<'a, T>>,
+    }
+
+    impl<T: ?Sized> Deref for TrackedRefMut<'_, T> {
+        type Target = T;
+
+        #[inline(always)]
+        fn deref(&self) -> &T {
+            &self.guard
+        }
+    }
+
+    impl<T: ?Sized> DerefMut for TrackedRefMut<'_, T> {
+        #[inline(always)]
+        fn deref_mut(&mut self) -> &mut T {
+            &mut self.guard
+        }
+    }
+
+    impl<T: ?Sized> Drop for TrackedRefMut<'_, T> {
+        fn drop(&mut self) {
+            // SAFETY: the guard is dropped exactly once, here.
+            unsafe { ManuallyDrop::drop(&mut self.guard) };
+            self.cell.release();
+        }
+    }
+
+    impl<T: ?Sized + fmt::Debug> fmt::Debug for TrackedRefMut<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(&**self, f)
+        }
+    }
+}
+
+impl<T: Default> Default for TrackedRefCell<T> {
+    #[inline]
+    fn default() -> Self {
+        TrackedRefCell::new(T::default())
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for TrackedRefCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("TrackedRefCell");
+        match self.inner.try_borrow() {
+            Ok(value) => d.field("value", &&*value),
+            Err(_) => d.field("value", &format_args!("<borrowed>")),
+        };
+        if let Some(site) = self.borrowed_at() {
+            d.field("borrowed_at", &format_args!("{}", site));
+        }
+        d.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borrows_behave_like_refcell() {
+        let cell = TrackedRefCell::new(vec![1u8]);
+        cell.borrow_mut().push(2);
+        {
+            let a = cell.borrow();
+            let b = cell.borrow();
+            assert_eq!((a.len(), b.len()), (2, 2));
+            assert!(cell.try_borrow_mut().is_err());
+        }
+        assert!(cell.borrowed_at().is_none());
+        assert_eq!(cell.into_inner(), [1, 2]);
+    }
+
+    #[cfg(all(feature = "debug_refcell", debug_assertions))]
+    #[test]
+    fn test_records_borrow_site() {
+        let cell = TrackedRefCell::new(0u32);
+        let (guard, line) = (cell.borrow_mut(), line!());
+
+        let site = cell.borrowed_at().unwrap();
+        assert_eq!((site.file(), site.line()), (file!(), line));
+        drop(guard);
+        assert!(cell.borrowed_at().is_none());
+
+        let (_first, _) = (cell.borrow(), line!());
+        let (_second, line) = (cell.borrow(), line!());
+        assert_eq!(cell.borrowed_at().unwrap().line(), line);
+    }
+
+    #[cfg(all(feature = "debug_refcell", debug_assertions))]
+    #[test]
+    fn test_conflict_names_leaked_borrow() {
+        let cell = TrackedRefCell::new(String::new());
+        let (leaked, line) = (cell.borrow_mut(), line!());
+        let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            drop(cell.borrow());
+        }))
+        .unwrap_err();
+        drop(leaked);
+
+        let message = err.downcast_ref::<String>().unwrap();
+        assert!(message.contains(&format!("at {}:{}:", file!(), line)), "{}", message);
+    }
+}
@@ -0,0 +1,201 @@
+//! Shared state with snapshot-based transactions.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::MutexExt;
+
+/// A shared value supporting nested `begin`/`commit`/`rollback`.
+///
+/// [`begin`](Transactional::begin) pushes a snapshot of the current value,
+/// [`commit`](Transactional::commit) discards the most recent snapshot and
+/// [`rollback`](Transactional::rollback) restores it. Transactions nest, and
+/// each commit or rollback pairs with the latest unmatched `begin`.
+///
+/// Clones share the same value and snapshot stack. The lock is recovered if
+/// poisoned, so a panicking writer can still be rolled back.
+///
+/// # Example
+///
+/// ```
+/// #[cfg(feature = "sync")]
+/// {
+///     use autowrap::Transactional;
+///
+///     let balance = Transactional::new(100i64);
+///     balance.begin();
+///     balance.with(|b| *b -= 30);
+///     balance.rollback();
+///     assert_eq!(balance.get(), 100);
+/// }
+/// ```
+pub struct Transactional<T: Clone> {
+    inner: Arc<Mutex<(T, Vec<T>)>>,
+}
+
+impl<T: Clone> Transactional<T> {
+    /// Creates a new value with no open transaction.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Transactional {
+            inner: Arc::new(Mutex::new((value, Vec::new()))),
+        }
+    }
+
+    /// Opens a transaction by snapshotting the current value.
+    pub fn begin(&self) {
+        let mut inner = self.inner.lock_ignore_poison();
+        let snapshot = inner.0.clone();
+        inner.1.push(snapshot);
+    }
+
+    /// Closes the innermost transaction, keeping the current value.
+    ///
+    /// Returns `false` if no transaction was open.
+    pub fn commit(&self) -> bool {
+        self.inner.lock_ignore_poison().1.pop().is_some()
+    }
+
+    /// Closes the innermost transaction, restoring the value it snapshotted.
+    ///
+    /// Returns `false` if no transaction was open.
+    pub fn rollback(&self) -> bool {
+        let mut inner = self.inner.lock_ignore_poison();
+        match inner.1.pop() {
+            Some(snapshot) => {
+                inner.0 = snapshot;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Runs `f` with mutable access to the current value.
+    pub fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        f(&mut self.inner.lock_ignore_poison().0)
+    }
+
+    /// Returns a clone of the current value.
+    pub fn get(&self) -> T {
+        self.inner.lock_ignore_poison().0.clone()
+    }
+
+    /// Returns the number of open transactions.
+    pub fn depth(&self) -> usize {
+        self.inner.lock_ignore_poison().1.len()
+    }
+}
+
+impl<T: Clone> Clone for Transactional<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Transactional {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: Clone + fmt::Debug> fmt::Debug for Transactional<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.lock_ignore_poison();
+        f.debug_struct("Transactional")
+            .field("value", &inner.0)
+            .field("depth", &inner.1.len())
+            .finish()
+    }
+}
+
+/// Serializes the current value; open transactions are not persisted.
+#[cfg(feature = "serde")]
+impl<T: Clone + serde::Serialize> serde::Serialize for Transactional<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.inner.lock_ignore_poison().0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Clone + serde::Deserialize<'de>> serde::Deserialize<'de> for Transactional<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Transactional::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_rollback_restores() {
+        let list = Transactional::new(vec![1, 2]);
+        list.begin();
+        list.with(|v| v.push(3));
+        assert_eq!(list.get(), [1, 2, 3]);
+
+        assert!(list.rollback());
+        assert_eq!(list.get(), [1, 2]);
+        assert!(!list.rollback());
+    }
+
+    #[test]
+    fn test_commit_keeps_changes() {
+        let value = Transactional::new(0u32);
+        value.begin();
+        value.with(|v| *v = 5);
+        assert!(value.commit());
+        assert_eq!(value.get(), 5);
+        assert_eq!(value.depth(), 0);
+        assert!(!value.commit());
+    }
+
+    #[test]
+    fn test_nested_transactions() {
+        let value = Transactional::new(String::from("a"));
+        value.begin();
+        value.with(|s| s.push('b'));
+        value.begin();
+        value.with(|s| s.push('c'));
+        assert_eq!(value.depth(), 2);
+
+        value.rollback();
+        assert_eq!(value.get(), "ab");
+        value.commit();
+        assert_eq!(value.get(), "ab");
+    }
+
+    #[test]
+    fn test_rollback_after_panicking_writer() {
+        let value = Transactional::new(10i32);
+        value.begin();
+
+        let writer = value.clone();
+        let result = thread::spawn(move || {
+            writer.with(|v| {
+                *v = -1;
+                panic!("writer failed midway");
+            })
+        })
+        .join();
+        assert!(result.is_err());
+        assert_eq!(value.get(), -1);
+
+        assert!(value.rollback());
+        assert_eq!(value.get(), 10);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let value = Transactional::new(1u32);
+        value.begin();
+        value.with(|v| *v = 2);
+        assert_eq!(serde_json::to_string(&value).unwrap(), "2");
+
+        let back: Transactional<u32> = serde_json::from_str("2").unwrap();
+        assert_eq!(back.get(), 2);
+        assert_eq!(back.depth(), 0);
+    }
+}
@@ -0,0 +1,13 @@
+//! The 64- and 128-bit atomic types behind the atomic extension traits.
+//!
+//! By default these are `core`'s own, which exist only on targets with
+//! native 64-bit atomics (and never at 128 bits on stable). With the
+//! `portable-atomic` feature every type comes from `portable_atomic`
+//! instead, which uses native instructions where the target has them and
+//! emulates the rest.
+
+#[cfg(all(not(feature = "portable-atomic"), target_has_atomic = "64"))]
+pub(crate) use core::sync::atomic::{AtomicI64, AtomicU64};
+
+#[cfg(feature = "portable-atomic")]
+pub(crate) use portable_atomic::{AtomicI128, AtomicI64, AtomicU128, AtomicU64};
@@ -0,0 +1,72 @@
+#![cfg(feature = "derive")]
+
+use autowrap::WrapFields;
+use std::sync::Arc;
+use std::thread;
+
+#[derive(WrapFields)]
+pub struct Config {
+    pub name: String,
+    #[wrap(arc_rwlock)]
+    pub routes: Vec<&'static str>,
+    #[wrap(arc)]
+    pub port: u16,
+}
+
+#[derive(WrapFields)]
+struct Local(#[wrap(rc_refcell)] Vec<u32>, #[wrap(rc)] &'static str);
+
+#[derive(WrapFields)]
+struct Slot<T>
+where
+    T: Copy,
+{
+    value: T,
+}
+
+#[derive(WrapFields)]
+struct Pair<T: Clone>(T, #[wrap(arc)] T);
+
+#[test]
+fn test_shared_field_mutated_across_threads() {
+    let shared = Config {
+        name: String::from("api"),
+        routes: vec!["/"],
+        port: 8080,
+    }
+    .into_shared();
+
+    let handle = shared.clone();
+    assert!(Arc::ptr_eq(&shared.port, &handle.port));
+    thread::spawn(move || {
+        handle.name.lock().unwrap().push_str("-v2");
+        handle.routes.write().unwrap().push("/health");
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(*shared.name.lock().unwrap(), "api-v2");
+    assert_eq!(*shared.routes.read().unwrap(), ["/", "/health"]);
+    assert_eq!(*shared.port, 8080);
+}
+
+#[test]
+fn test_tuple_struct_local_wrappers() {
+    let local = Local(vec![1], "tag").into_shared();
+    let alias = local.clone();
+    alias.0.borrow_mut().push(2);
+    assert_eq!(*local.0.borrow(), [1, 2]);
+    assert_eq!(*local.1, "tag");
+    assert_eq!(std::rc::Rc::strong_count(&local.1), 2);
+}
+
+#[test]
+fn test_generic_struct() {
+    let slot = Slot { value: 5u8 }.into_shared();
+    *slot.value.lock().unwrap() += 1;
+    assert_eq!(*slot.value.lock().unwrap(), 6);
+
+    let pair = Pair(String::from("a"), String::from("b")).into_shared();
+    assert_eq!(*pair.0.lock().unwrap(), "a");
+    assert_eq!(*pair.1, "b");
+}
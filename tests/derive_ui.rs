@@ -0,0 +1,8 @@
+#![cfg(feature = "derive")]
+
+#[test]
+fn test_derive_ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass_*.rs");
+    t.compile_fail("tests/ui/fail_*.rs");
+}
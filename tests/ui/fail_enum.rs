@@ -0,0 +1,9 @@
+use autowrap::WrapFields;
+
+#[derive(WrapFields)]
+enum Mode {
+    Fast,
+    Slow,
+}
+
+fn main() {}
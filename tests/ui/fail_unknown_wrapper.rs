@@ -0,0 +1,9 @@
+use autowrap::WrapFields;
+
+#[derive(WrapFields)]
+struct Config {
+    #[wrap(box_mutex)]
+    name: String,
+}
+
+fn main() {}
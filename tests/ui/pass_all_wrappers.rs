@@ -0,0 +1,30 @@
+use autowrap::WrapFields;
+
+#[derive(WrapFields)]
+struct Everything {
+    a: u8,
+    #[wrap(arc_mutex)]
+    b: u8,
+    #[wrap(arc_rwlock)]
+    c: u8,
+    #[wrap(arc)]
+    d: u8,
+    #[wrap(rc_refcell)]
+    e: u8,
+    #[wrap(rc)]
+    f: u8,
+}
+
+#[derive(WrapFields)]
+struct Unit;
+
+fn main() {
+    let shared = Everything { a: 1, b: 2, c: 3, d: 4, e: 5, f: 6 }.into_shared();
+    let _: std::sync::Arc<std::sync::Mutex<u8>> = shared.a;
+    let _: std::sync::Arc<std::sync::Mutex<u8>> = shared.b;
+    let _: std::sync::Arc<std::sync::RwLock<u8>> = shared.c;
+    let _: std::sync::Arc<u8> = shared.d;
+    let _: std::rc::Rc<std::cell::RefCell<u8>> = shared.e;
+    let _: std::rc::Rc<u8> = shared.f;
+    let _: UnitShared = Unit.into_shared();
+}
@@ -11,6 +11,95 @@ mod tests {
         assert_eq!(c.get(), 20);
     }
 
+    #[test]
+    fn test_cell_move_wrap() {
+        let items = vec![1u8].cell_move();
+        assert_eq!(items.replace(vec![2, 3]), [1]);
+        assert_eq!(items.into_inner(), [2, 3]);
+    }
+
+    #[test]
+    fn test_cell_option_wrap() {
+        use autowrap::CellOptionExt;
+
+        let slot = vec![1u8].cell_option();
+        assert_eq!(slot.take_value(), Some(vec![1]));
+        slot.put(vec![2]);
+        assert_eq!(slot.take_value(), Some(vec![2]));
+        assert_eq!(slot.take_value(), None);
+    }
+
+    #[test]
+    fn test_non_zero_wrap() {
+        use autowrap::NonZeroWrapExt;
+        use std::num::NonZeroU32;
+
+        assert_eq!(0u32.non_zero(), None);
+        assert_eq!(5u32.non_zero(), NonZeroU32::new(5));
+        assert!(core::mem::size_of::<Option<NonZeroU32>>() == core::mem::size_of::<u32>());
+    }
+
+    #[test]
+    fn test_manually_drop_wrap() {
+        use std::mem::ManuallyDrop;
+
+        let name = String::from("kept").manually_drop();
+        assert_eq!(name.len(), 4);
+        assert_eq!(ManuallyDrop::into_inner(name), "kept");
+    }
+
+    #[test]
+    fn test_maybe_uninit_wrap() {
+        let name = String::from("ready").maybe_uninit();
+        // SAFETY: `maybe_uninit` always fully initializes the value.
+        let name = unsafe { name.assume_init() };
+        assert_eq!(name, "ready");
+    }
+
+    #[test]
+    fn test_once_cell_wrap() {
+        use autowrap::{once_cell_empty, OnceCellExt};
+
+        let name = String::from("set").once_cell();
+        assert_eq!(name.get().map(String::as_str), Some("set"));
+
+        let lazy = once_cell_empty::<String>();
+        assert!(lazy.get().is_none());
+        assert_eq!(lazy.get_or_wrap(|| "first".into()), "first");
+        assert_eq!(lazy.get_or_wrap(|| "second".into()), "first");
+    }
+
+    #[test]
+    fn test_unsafe_cell_wrap() {
+        let cell = String::from("raw").unsafe_cell();
+        let ptr: *const String = cell.get();
+        // SAFETY: nothing else accesses the cell while we read through `ptr`.
+        let value = unsafe { &*ptr };
+        assert_eq!(value, "raw");
+    }
+
+    #[test]
+    fn test_cell_pair_wrap() {
+        let (width, height) = (800u32, 600u32).cell_pair();
+        width.set(1024);
+        assert_eq!(width.get(), 1024);
+        assert_eq!(height.get(), 600);
+    }
+
+    #[test]
+    fn test_option_result_wrap() {
+        fn parse(input: &str) -> Result<u32, &'static str> {
+            match input.parse::<u32>() {
+                Ok(n) => n.ok(),
+                Err(_) => "not a number".err(),
+            }
+        }
+
+        assert_eq!(3u32.some().map(|n| n * 2), Some(6));
+        assert_eq!(parse("12"), Ok(12));
+        assert_eq!(parse("x"), Err("not a number"));
+    }
+
     #[cfg(feature = "std")]
     mod std_tests {
         use std::rc::Rc;
@@ -32,6 +121,29 @@ mod tests {
             assert_eq!(Rc::strong_count(&rc2), 2);
         }
 
+        #[cfg(feature = "allocator_api")]
+        #[test]
+        fn test_try_rc() {
+            let rc = String::from("kept").try_rc().unwrap();
+            assert_eq!(*rc, "kept");
+            assert_eq!(Rc::strong_count(&rc), 1);
+            assert_eq!(*5u32.try_boxed().unwrap(), 5);
+        }
+
+        #[test]
+        fn test_boxed_slice_repeat() {
+            let names = String::from("slot").boxed_slice_repeat(3);
+            assert_eq!(names.len(), 3);
+            assert!(names.iter().all(|name| name == "slot"));
+        }
+
+        #[test]
+        fn test_rc_pair_wrap() {
+            let (a, b) = String::from("shared").rc_pair();
+            assert_eq!(Rc::strong_count(&a), 2);
+            assert_eq!(*a, *b);
+        }
+
         #[test]
         fn test_rc_refcell_wrap() {
             let value = 100u32.rc_refcell();
@@ -42,10 +154,26 @@ mod tests {
             assert_eq!(Rc::strong_count(&cloned), 2);
         }
 
+        #[test]
+        fn test_rc_cell_shared_wrap() {
+            let value = String::from("a").rc_cell_shared();
+            let other = value.clone();
+            other.with_mut(|s| s.push('b'));
+            assert_eq!(*value.borrow(), "ab");
+        }
+
+        #[test]
+        fn test_tracked_refcell_wrap() {
+            let value = vec![1u8].tracked_refcell();
+            value.borrow_mut().push(2);
+            assert_eq!(*value.borrow(), [1, 2]);
+            assert!(value.borrowed_at().is_none());
+        }
+
         #[cfg(feature = "sync")]
         mod sync_tests {
             use super::*;
-            use std::sync::{Arc, Mutex};
+            use std::sync::Arc;
             use std::thread;
 
             #[test]
@@ -57,6 +185,81 @@ mod tests {
                 assert_eq!(Arc::strong_count(&arc2), 2);
             }
 
+            #[cfg(feature = "allocator_api")]
+            #[test]
+            fn test_try_arc() {
+                let arc = String::from("shared").try_arc().unwrap();
+                assert_eq!(*arc, "shared");
+                assert_eq!(Arc::strong_count(&arc), 1);
+            }
+
+            #[test]
+            fn test_arc_pair_wrap() {
+                let (keep, send) = vec![1, 2, 3].arc_pair();
+                assert_eq!(Arc::strong_count(&keep), 2);
+
+                let sum = thread::spawn(move || send.iter().sum::<i32>()).join().unwrap();
+                assert_eq!(sum, 6);
+                assert_eq!(Arc::strong_count(&keep), 1);
+            }
+
+            #[test]
+            fn test_arc_cow_wrap() {
+                let original = String::from("base").arc_cow();
+                let mut edited = original.clone();
+                edited.make_mut().push_str("-edit");
+                assert_eq!(original.get(), "base");
+                assert_eq!(edited.get(), "base-edit");
+            }
+
+            #[test]
+            fn test_monitor_wrap() {
+                let jobs_left = 2u32.monitor();
+                thread::scope(|s| {
+                    for _ in 0..2 {
+                        s.spawn(|| jobs_left.notify_all(|left| *left -= 1));
+                    }
+                    assert_eq!(*jobs_left.wait_until(|left| *left == 0), 0);
+                });
+            }
+
+            #[test]
+            fn test_swap_slot_wrap() {
+                let slot = 1u32.swap_slot();
+                let old = slot.load();
+                slot.store(Arc::new(2));
+                assert_eq!((*old, *slot.load()), (1, 2));
+            }
+
+            #[test]
+            fn test_small_arc_wrap() {
+                let small = 3u16.small_arc();
+                let large = [1u64; 4].small_arc();
+                assert!(small.is_inline());
+                assert!(!large.is_inline());
+                assert_eq!((small.clone().get(), large.clone()[3]), (3, 1));
+            }
+
+            #[test]
+            fn test_tracked_mutex_wrap() {
+                let value = 1u32.tracked_mutex();
+                *value.lock().unwrap() += 1;
+                assert_eq!(*value.lock().unwrap(), 2);
+            }
+
+            #[test]
+            fn test_arc_prefer_writer_rwlock_wrap() {
+                let value = vec![1u32].arc_prefer_writer_rwlock();
+                value.write().push(2);
+                assert_eq!(*value.read(), [1, 2]);
+            }
+
+            #[test]
+            fn test_arc_mutex_init_wrap() {
+                let value = vec![1u32].arc_mutex_init(|v| v.extend([2, 3]));
+                assert_eq!(*value.lock().unwrap(), [1, 2, 3]);
+            }
+
             #[test]
             fn test_arc_mutex_wrap() {
                 let value = 1u32.arc_mutex();
@@ -77,6 +280,235 @@ mod tests {
                 handle.join().unwrap();
                 assert_eq!(*value.lock().unwrap(), 15);
             }
+
+            #[test]
+            fn test_atomic_enum_wrap() {
+                use autowrap::AtomicEnumRepr;
+                use std::sync::atomic::Ordering;
+
+                #[derive(Clone, Copy, Debug, PartialEq)]
+                enum Light {
+                    Off,
+                    On,
+                }
+
+                impl AtomicEnumRepr for Light {
+                    fn into_repr(self) -> usize {
+                        self as usize
+                    }
+
+                    fn from_repr(repr: usize) -> Option<Self> {
+                        [Light::Off, Light::On].get(repr).copied()
+                    }
+                }
+
+                let light = Light::Off.atomic_enum();
+                light.store(Light::On, Ordering::Release);
+                assert_eq!(light.load(Ordering::Acquire), Light::On);
+            }
+
+            #[cfg(not(feature = "loom"))]
+            #[test]
+            fn test_atomic_cell_lock_free_and_locked() {
+                use autowrap::AtomicCell;
+
+                let small = [1u32, 2].atomic_cell();
+                assert!(AtomicCell::<[u32; 2]>::is_lock_free());
+                assert_eq!(small.swap([3, 4]), [1, 2]);
+
+                let large = [0u32; 5].atomic_cell();
+                assert!(!AtomicCell::<[u32; 5]>::is_lock_free());
+                large.store([1; 5]);
+                assert_eq!(large.load(), [1; 5]);
+            }
+
+            #[cfg(any(target_has_atomic = "64", feature = "portable-atomic"))]
+            #[test]
+            fn test_atomic_array_wrap() {
+                use autowrap::ArrayAtomicExt;
+                use std::sync::atomic::Ordering;
+
+                let ring = [10u64, 20, 30, 40, 50, 60, 70, 80].atomic_array();
+                ring[0].fetch_add(1, Ordering::SeqCst);
+                let loaded: Vec<u64> = ring.iter().map(|a| a.load(Ordering::SeqCst)).collect();
+                assert_eq!(loaded, [11, 20, 30, 40, 50, 60, 70, 80]);
+
+                let one = [u8::MAX].atomic_array();
+                assert_eq!(one[0].load(Ordering::SeqCst), u8::MAX);
+            }
+
+            #[test]
+            fn test_atomic_ptr_wrap() {
+                use autowrap::{AtomicPtrExt, AtomicPtrWrapExt};
+                use std::sync::atomic::Ordering;
+
+                let ptr = std::ptr::null_mut::<String>().atomic_ptr();
+                assert!(ptr.load(Ordering::SeqCst).is_null());
+
+                let raw = Box::into_raw(Box::new(String::from("node")));
+                assert_eq!(ptr.compare_exchange_ptr(std::ptr::null_mut(), raw), Ok(std::ptr::null_mut()));
+
+                let taken = ptr.swap_ptr(std::ptr::null_mut());
+                // SAFETY: `taken` is the pointer produced by `Box::into_raw` above.
+                let boxed = unsafe { Box::from_raw(taken) };
+                assert_eq!(*boxed, "node");
+            }
+
+            #[test]
+            fn test_arc_dyn_closures() {
+                let offset = 10;
+                let callbacks: Vec<Arc<dyn Fn() -> i32 + Send + Sync>> = vec![
+                    autowrap::arc_dyn!(dyn Fn() -> i32 + Send + Sync, || 1),
+                    autowrap::arc_dyn!(dyn Fn() -> i32 + Send + Sync, move || offset),
+                    autowrap::arc_dyn!(dyn Fn() -> i32 + Send + Sync, || -3),
+                ];
+
+                let results: Vec<i32> = callbacks.iter().map(|f| f()).collect();
+                assert_eq!(results, [1, 10, -3]);
+
+                let shared = Arc::clone(&callbacks[1]);
+                let handle = thread::spawn(move || shared());
+                assert_eq!(handle.join().unwrap(), 10);
+            }
+
+            #[test]
+            fn test_pin_arc_wrap() {
+                let pinned = String::from("pinned").pin_arc();
+                let other = std::pin::Pin::clone(&pinned);
+                assert_eq!(other.as_str(), "pinned");
+                assert!(std::ptr::eq(&*pinned, &*other));
+            }
+
+            #[test]
+            fn test_arc_pin_dyn_shared_address() {
+                use std::pin::Pin;
+
+                trait Task {
+                    fn step(&self) -> u32;
+                }
+
+                struct Countdown(u32);
+
+                impl Task for Countdown {
+                    fn step(&self) -> u32 {
+                        self.0 - 1
+                    }
+                }
+
+                let task = autowrap::arc_pin_dyn!(Task, Countdown(3));
+                let addr = &*task as *const dyn Task as *const () as usize;
+
+                let shared = Pin::clone(&task);
+                let handle = thread::spawn(move || {
+                    (shared.step(), &*shared as *const dyn Task as *const () as usize)
+                });
+                let (step, remote_addr) = handle.join().unwrap();
+
+                assert_eq!(step, 2);
+                assert_eq!(remote_addr, addr);
+            }
+
+            #[test]
+            fn test_new_cyclic_two_node_cycle() {
+                use std::sync::atomic::{AtomicUsize, Ordering};
+                use std::sync::Weak;
+
+                struct Node {
+                    name: &'static str,
+                    peer: Weak<Node>,
+                    drops: Arc<AtomicUsize>,
+                }
+
+                impl Drop for Node {
+                    fn drop(&mut self) {
+                        self.drops.fetch_add(1, Ordering::SeqCst);
+                    }
+                }
+
+                let drops = Arc::new(AtomicUsize::new(0));
+                let mut second = None;
+                let first = autowrap::new_cyclic_wrap(|me: &Weak<Node>| {
+                    let other = Arc::new(Node {
+                        name: "b",
+                        peer: me.clone(),
+                        drops: Arc::clone(&drops),
+                    });
+                    let peer = Arc::downgrade(&other);
+                    second = Some(other);
+                    Node {
+                        name: "a",
+                        peer,
+                        drops: Arc::clone(&drops),
+                    }
+                });
+                let second = second.unwrap();
+
+                assert_eq!(first.peer.upgrade().unwrap().name, "b");
+                assert_eq!(second.peer.upgrade().unwrap().name, "a");
+                assert_eq!(Arc::strong_count(&first), 1);
+                assert_eq!(Arc::strong_count(&second), 1);
+
+                let (weak_first, weak_second) = (Arc::downgrade(&first), Arc::downgrade(&second));
+                drop(first);
+                drop(second);
+
+                assert!(weak_first.upgrade().is_none());
+                assert!(weak_second.upgrade().is_none());
+                assert_eq!(drops.load(Ordering::SeqCst), 2);
+            }
+
+            #[test]
+            fn test_arc_mutex_vec_queue() {
+                use autowrap::CollectionWrapExt;
+
+                let queue = Vec::<u32>::new().arc_mutex_vec();
+                let producer = {
+                    let queue = Arc::clone(&queue);
+                    thread::spawn(move || {
+                        for i in 0..100 {
+                            queue.lock().unwrap().push(i);
+                        }
+                    })
+                };
+
+                let mut popped = Vec::new();
+                while popped.len() < 100 {
+                    if let Some(item) = queue.lock().unwrap().pop() {
+                        popped.push(item);
+                    }
+                }
+                producer.join().unwrap();
+
+                popped.sort_unstable();
+                assert_eq!(popped, (0..100).collect::<Vec<_>>());
+                assert!(queue.lock().unwrap().is_empty());
+            }
+
+            #[test]
+            fn test_arc_rwlock_hashmap_reads() {
+                use autowrap::CollectionWrapExt;
+                use std::collections::HashMap;
+
+                let map: HashMap<u32, String> = (0..10).map(|i| (i, i.to_string())).collect();
+                let map = map.arc_rwlock_hashmap();
+
+                let readers: Vec<_> = (0..4)
+                    .map(|_| {
+                        let map = Arc::clone(&map);
+                        thread::spawn(move || {
+                            (0..1000u32)
+                                .map(|i| map.read().unwrap()[&(i % 10)].len())
+                                .sum::<usize>()
+                        })
+                    })
+                    .collect();
+                map.write().unwrap().insert(10, "10".into());
+
+                for reader in readers {
+                    assert_eq!(reader.join().unwrap(), 1000);
+                }
+                assert_eq!(map.read().unwrap().len(), 11);
+            }
         }
     }
 }
\ No newline at end of file